@@ -0,0 +1,94 @@
+//! Compares converting a large nested payload directly with going through
+//! `serde_json::Value`, which is how `Value::serialize` and
+//! `Value::deserialize` used to work.
+//!
+//! Run with `cargo bench --bench serde`.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use unijs::{Module, Scope, Value};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Order {
+    id: u64,
+    customer: String,
+    paid: bool,
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Item {
+    sku: String,
+    quantity: u32,
+    price: f64,
+    tags: Vec<String>,
+}
+
+fn payload(orders: usize) -> Vec<Order> {
+    (0..orders)
+        .map(|id| Order {
+            id: id as u64,
+            customer: format!("customer {}", id % 97),
+            paid: id % 3 == 0,
+            items: (0..10)
+                .map(|item| Item {
+                    sku: format!("SKU-{:05}", id * 10 + item),
+                    quantity: item as u32 + 1,
+                    price: item as f64 * 1.25,
+                    tags: vec!["fragile".to_owned(), "gift".to_owned()],
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn time<T>(name: &str, iterations: u32, mut f: impl FnMut() -> T) -> Duration {
+    // warm up the engine and the allocator
+    f();
+    let start = Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(f());
+    }
+    let elapsed = start.elapsed() / iterations;
+    println!("{:<32} {:>10.3?}", name, elapsed);
+    elapsed
+}
+
+fn serialize_via_json(scope: &mut Scope, orders: &[Order]) -> Value {
+    let json = serde_json::to_value(orders).unwrap();
+    Value::from_json(scope, json).unwrap()
+}
+
+fn deserialize_via_json(scope: &mut Scope, value: Value) -> Vec<Order> {
+    let json = value.into_json(scope).unwrap().unwrap();
+    serde_json::from_value(json).unwrap()
+}
+
+fn main() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+
+    for orders in [100, 1_000, 10_000] {
+        let iterations = (100_000 / orders) as u32;
+        let orders_payload = payload(orders);
+        println!("{} orders, {} items", orders, orders * 10);
+
+        let direct = time("  serialize", iterations, || {
+            Value::serialize(&mut scope, &orders_payload).unwrap()
+        });
+        let via_json = time("  serialize via serde_json", iterations, || {
+            serialize_via_json(&mut scope, &orders_payload)
+        });
+        println!("  speedup {:.1}x", via_json.as_secs_f64() / direct.as_secs_f64());
+
+        let value = Value::serialize(&mut scope, &orders_payload).unwrap();
+        let direct = time("  deserialize", iterations, || {
+            value.clone().deserialize::<Vec<Order>>(&mut scope).unwrap()
+        });
+        let via_json = time("  deserialize via serde_json", iterations, || {
+            deserialize_via_json(&mut scope, value.clone())
+        });
+        println!("  speedup {:.1}x", via_json.as_secs_f64() / direct.as_secs_f64());
+    }
+}
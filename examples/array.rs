@@ -0,0 +1,45 @@
+use tracing::{info, Level};
+use unijs::{Array, Module, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.sum = function(numbers) {
+            return numbers.reduce((sum, number) => sum + number, 0);
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+
+    let numbers = Array::from_iter(&mut scope, (1..=5).map(|i| Value::Number(i as f64))).unwrap();
+    numbers
+        .extend(&mut scope, [Value::Number(6.0), Value::Number(7.0)])
+        .unwrap();
+    info!("pop: {:?}", numbers.pop(&mut scope).unwrap());
+    numbers.insert(&mut scope, 0, Value::Number(0.0)).unwrap();
+    info!("remove: {:?}", numbers.remove(&mut scope, 1).unwrap());
+    assert!(numbers.remove(&mut scope, 100).unwrap().is_none());
+
+    let removed = numbers
+        .splice(&mut scope, 1, 2, [Value::Number(10.0)])
+        .unwrap();
+    info!("spliced out: {:?}", removed.to_vec(&mut scope).unwrap());
+    info!("numbers: {:?}", numbers.to_vec(&mut scope).unwrap());
+
+    let middle = numbers.slice(&mut scope, 1, 3).unwrap();
+    info!("slice: {:?}", middle.to_vec(&mut scope).unwrap());
+
+    numbers.truncate(&mut scope, 3).unwrap();
+    assert_eq!(numbers.length(&mut scope), 3);
+    for number in numbers.iter(&mut scope) {
+        info!("item: {:?}", number.unwrap());
+    }
+
+    let sum = exports.get(&mut scope, "sum").unwrap().into_function().unwrap();
+    let values = [1.5, 2.5, 3.0].map(Value::Number);
+    let numbers = Array::from_slice(&mut scope, &values).unwrap();
+    info!("sum: {:?}", sum.call(&mut scope, &[numbers.into()]).unwrap());
+}
@@ -0,0 +1,48 @@
+use tracing::{info, Level};
+use unijs::{Function, Module, Value};
+
+async fn lookup_price(item: String) -> Result<f64, String> {
+    match item.as_str() {
+        "apple" => Ok(0.5),
+        "pear" => Ok(0.75),
+        _ => Err(format!("unknown item: {}", item)),
+    }
+}
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.total = async function(lookupPrice, items) {
+            let total = 0;
+            for (const item of items) {
+                try {
+                    total += await lookupPrice(item);
+                } catch {
+                    // unknown items are skipped
+                }
+            }
+            return total;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let total = exports.get(&mut scope, "total").unwrap().into_function().unwrap();
+    let price = Function::new_async(&mut scope, |args| async move {
+        let item = args.get(0).into_string().unwrap_or_default();
+        lookup_price(item)
+            .await
+            .map(Value::Number)
+            .map_err(Value::String)
+    });
+    let items = Value::from_json(&mut scope, serde_json::json!(["apple", "kiwi", "pear"])).unwrap();
+    let promise = total
+        .call(&mut scope, &[price.into(), items])
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    let total = promise.into_future(&mut scope).await.unwrap();
+    info!("total: {:?}", total);
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use tracing::{info, Level};
+use unijs::{BigInt, Module, SerializeOptions, Value};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Account {
+    id: u64,
+    name: String,
+}
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.big = 2n ** 100n;
+        exports.factorial = function(n) {
+            let result = 1n;
+            for (let i = 2n; i <= n; i++) {
+                result *= i;
+            }
+            return result;
+        }
+        exports.describe = function(account) {
+            return `${account.name} (${typeof account.id} ${account.id})`;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+
+    let big = exports.get(&mut scope, "big").unwrap().into_bigint().unwrap();
+    info!("2n ** 100n = {}", big);
+    assert_eq!(big.to_u128(), Some(1 << 100));
+
+    let factorial = exports
+        .get(&mut scope, "factorial")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let result = factorial
+        .call(&mut scope, &[BigInt::from(20i64).into()])
+        .unwrap()
+        .into_bigint()
+        .unwrap();
+    info!("20! = {} ({:?} as i64)", result, result.to_i64());
+    let result = factorial
+        .call(&mut scope, &[BigInt::from(30i128).into()])
+        .unwrap()
+        .into_bigint()
+        .unwrap();
+    info!("30! = {} ({:?} as i64)", result, result.to_i64());
+
+    // ids above Number.MAX_SAFE_INTEGER would lose precision as numbers
+    let account = Account {
+        id: u64::MAX - 1,
+        name: "jabu".to_owned(),
+    };
+    let options = SerializeOptions {
+        large_integers_as_bigint: true,
+    };
+    let value = Value::serialize_with_options(&mut scope, &account, &options).unwrap();
+    let describe = exports
+        .get(&mut scope, "describe")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let description = describe.call(&mut scope, &[value.clone()]).unwrap();
+    info!("{:?}", description);
+    let account: Account = value.deserialize(&mut scope).unwrap();
+    assert_eq!(account.id, u64::MAX - 1);
+    info!("{:?}", account);
+}
@@ -0,0 +1,53 @@
+use tracing::{info, Level};
+use unijs::{Module, TypedArray, TypedArrayKind};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.checksum = function(bytes) {
+            let sum = 0;
+            for (const byte of bytes) {
+                sum = (sum + byte) % 256;
+            }
+            return sum;
+        }
+        exports.invert = function(bytes) {
+            for (let i = 0; i < bytes.length; i++) {
+                bytes[i] = 255 - bytes[i];
+            }
+        }
+        exports.samples = new Float32Array([0.5, -1, 2.25]);
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let checksum = exports.get(&mut scope, "checksum").unwrap().into_function().unwrap();
+    let invert = exports.get(&mut scope, "invert").unwrap().into_function().unwrap();
+
+    // the vec is handed over to javascript without copying
+    let bytes = TypedArray::from_vec(&mut scope, (0..=255).collect());
+    let sum = checksum.call(&mut scope, &[bytes.clone().into()]).unwrap();
+    info!("checksum: {:?}", sum);
+
+    invert.call(&mut scope, &[bytes.clone().into()]).unwrap();
+    bytes.with_bytes(&mut scope, |bytes| info!("inverted: {:?}", &bytes[..8]));
+    bytes.with_bytes_mut(&mut scope, |bytes| bytes.fill(7));
+    let sum = checksum.call(&mut scope, &[bytes.into()]).unwrap();
+    info!("checksum after fill: {:?}", sum);
+
+    let samples = exports
+        .get(&mut scope, "samples")
+        .unwrap()
+        .into_typed_array()
+        .unwrap();
+    assert_eq!(samples.kind(), TypedArrayKind::Float32);
+    let samples = samples.with_bytes(&mut scope, |bytes| {
+        bytes
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>()
+    });
+    info!("samples: {:?}", samples);
+}
@@ -0,0 +1,35 @@
+use tracing::{info, Level};
+use unijs::{MemoryModuleLoader, Runtime, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let loader = MemoryModuleLoader::new().with(
+        "lib/greeting.js",
+        r#"
+            const prefix = "hello";
+            module.exports = (name) => `${prefix} ${name} from ${__filename}`;
+        "#,
+    );
+
+    let mut runtime = Runtime::new();
+    runtime.set_module_loader(loader);
+    let module = runtime
+        .load(
+            r#"
+            const greet = require("./lib/greeting.js");
+            module.exports = function(name) {
+                return greet(name);
+            };
+        "#,
+        )
+        .unwrap();
+    let greet = module.exports().clone().into_function().unwrap();
+    let mut scope = runtime.scope(module.context());
+    let greeting = greet
+        .call(&mut scope, &[Value::String("world".to_owned())])
+        .unwrap();
+    info!("{:?}", greeting);
+}
@@ -0,0 +1,45 @@
+use std::{cell::RefCell, rc::Rc};
+
+use tracing::{info, Level};
+use unijs::{ConsoleLevel, ConsoleSink, Runtime};
+
+/// Collects console messages instead of logging them.
+#[derive(Default, Clone)]
+struct Collect(Rc<RefCell<Vec<(ConsoleLevel, String)>>>);
+
+impl ConsoleSink for Collect {
+    fn write(&self, level: ConsoleLevel, message: &str) {
+        self.0.borrow_mut().push((level, message.to_owned()));
+    }
+}
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::DEBUG, "");
+    unijs::init();
+
+    let js = r#"
+        console.log("hello %s, you are %d", "world", 42.5, { nested: { deeply: { hidden: true } } });
+        console.debug([1, "two", [3]], new Map([["a", 1]]), new Set([1, 2]));
+        console.warn("careful");
+        console.error(new Error("oops"));
+        console.assert(1 + 1 === 3, "math is broken");
+        console.table([{ a: 1, b: "x" }, { a: 2, c: true }]);
+        console.time("loop");
+        for (let i = 0; i < 1000; i++) {}
+        console.timeEnd("loop");
+        console.trace("here");
+    "#;
+
+    // messages go to tracing by default
+    let mut runtime = Runtime::new();
+    runtime.load(js).unwrap();
+
+    let collect = Collect::default();
+    let mut runtime = Runtime::new();
+    runtime.set_console_sink(collect.clone());
+    runtime.load(js).unwrap();
+    for (level, message) in collect.0.borrow().iter() {
+        info!("collected {:?}: {}", level, message);
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, Level};
+use unijs::{Date, Module, Object, Value};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Event {
+    name: String,
+    at: String,
+}
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.launch = new Date(Date.UTC(1969, 6, 20, 20, 17, 40));
+        exports.invalid = new Date("not a date");
+        exports.addDays = function(date, days) {
+            return new Date(date.getTime() + days * 24 * 60 * 60 * 1000);
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+
+    let launch = exports.get(&mut scope, "launch").unwrap().into_date().unwrap();
+    info!("launch: {}", launch.timestamp(&mut scope));
+    info!("launch: {:?}", launch.to_system_time(&mut scope));
+    info!("launch: {:?}", launch.to_iso_string(&mut scope));
+
+    let invalid = exports.get(&mut scope, "invalid").unwrap().into_date().unwrap();
+    info!("invalid: {}", invalid.is_valid(&mut scope));
+
+    let now = Date::from_system_time(&mut scope, SystemTime::now()).unwrap();
+    let add_days = exports
+        .get(&mut scope, "addDays")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let next_week = add_days
+        .call(&mut scope, &[now.into(), Value::Number(7.0)])
+        .unwrap()
+        .into_date()
+        .unwrap();
+    let next_week = next_week.to_system_time(&mut scope).unwrap();
+    info!(
+        "next week is in {:?}",
+        next_week
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO)
+    );
+
+    // dates deserialize as ISO strings
+    let event = Object::new(&mut scope);
+    event
+        .set(&mut scope, "name", Value::String("launch".to_owned()))
+        .unwrap();
+    event.set(&mut scope, "at", launch.into()).unwrap();
+    let event: Event = Value::Object(event).deserialize(&mut scope).unwrap();
+    info!("{:?}", event);
+}
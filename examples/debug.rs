@@ -21,13 +21,14 @@ async fn main() {
         }
     "#;
 
-    let (mut scope, exports) = Module::load(&js);
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
 
 
-    let values = exports.get(&mut scope, "values").into_function().unwrap();
-    let array = values.call(&mut scope, &[]).into_array().unwrap();
+    let values = exports.get(&mut scope, "values").unwrap().into_function().unwrap();
+    let array = values.call(&mut scope, &[]).unwrap().into_array().unwrap();
     for i in 0..array.length(&mut scope) {
-        info!("{:?}", array.get(&mut scope, i));
+        info!("{:?}", array.get(&mut scope, i).unwrap());
     }
 }
 
@@ -0,0 +1,32 @@
+use tracing::{error, info, Level};
+use unijs::{Module, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.divide = function(a, b) {
+            if (b === 0) {
+                throw new RangeError("division by zero");
+            }
+            return a / b;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let divide = exports.get(&mut scope, "divide").unwrap().into_function().unwrap();
+    match divide.call(&mut scope, &[Value::Number(10.0), Value::Number(4.0)]) {
+        Ok(value) => info!("{:?}", value),
+        Err(err) => error!("{}", err),
+    }
+    match divide.call(&mut scope, &[Value::Number(10.0), Value::Number(0.0)]) {
+        Ok(value) => info!("{:?}", value),
+        Err(err) => error!("{}\n{}", err, err.stack().unwrap_or_default()),
+    }
+
+    if let Err(err) = Module::load("exports.broken = ;") {
+        error!("{}", err);
+    }
+}
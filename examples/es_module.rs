@@ -0,0 +1,45 @@
+use tracing::{info, Level};
+use unijs::{MemoryModuleLoader, Runtime, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let loader = MemoryModuleLoader::new()
+        .with(
+            "lib/math.js",
+            r#"
+            export function square(x) {
+                return x * x;
+            }
+        "#,
+        )
+        .with(
+            "main.js",
+            r#"
+            import { square } from "./lib/math.js";
+            export const answer = square(6) + 6;
+            export default function greet(name) {
+                return "hello " + name;
+            }
+        "#,
+        );
+
+    let mut runtime = Runtime::new();
+    runtime.set_module_loader(loader);
+    let module = runtime.import("./main.js").await.unwrap();
+    let exports = module.exports().clone().into_object().unwrap();
+    let mut scope = runtime.scope(module.context());
+    let answer = exports.get(&mut scope, "answer").unwrap();
+    info!("answer: {:?}", answer);
+    let greet = exports
+        .get(&mut scope, "default")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let greeting = greet
+        .call(&mut scope, &[Value::String("world".to_owned())])
+        .unwrap();
+    info!("{:?}", greeting);
+}
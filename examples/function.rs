@@ -0,0 +1,50 @@
+use std::{cell::RefCell, rc::Rc};
+
+use tracing::{info, Level};
+use unijs::{Args, Function, Module, Scope, Value};
+
+fn parse_age(scope: &mut Scope, args: Args) -> Result<Value, Value> {
+    match args.get(0) {
+        Value::Number(age) if age >= 0.0 => Ok(Value::Number(age)),
+        value => Err(Value::error(scope, &format!("invalid age: {:?}", value))),
+    }
+}
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.check = function(parseAge, age) {
+            try {
+                return "ok: " + parseAge(age);
+            } catch (err) {
+                return "caught: " + err.message;
+            }
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let check = exports.get(&mut scope, "check").unwrap().into_function().unwrap();
+    let parse_age = Function::new(&mut scope, parse_age);
+    for age in [Value::Number(31.0), Value::Number(-1.0)] {
+        let result = check
+            .call(&mut scope, &[parse_age.clone().into(), age])
+            .unwrap();
+        info!("{:?}", result);
+    }
+
+    let calls = Rc::new(RefCell::new(0));
+    let counted = Function::new(&mut scope, {
+        let calls = calls.clone();
+        move |_scope, args| {
+            *calls.borrow_mut() += 1;
+            Ok(args.get(0))
+        }
+    });
+    check
+        .call(&mut scope, &[counted.into(), Value::Number(1.0)])
+        .unwrap();
+    info!("counted function called {} time(s)", calls.borrow());
+}
@@ -0,0 +1,35 @@
+use tracing::{info, Level};
+use unijs::{Module, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        globalThis.counter = 0;
+        exports.increment = function() {
+            return ++globalThis.counter;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let increment = exports
+        .get(&mut scope, "increment")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    for expected in 1..=3 {
+        let value = increment.call(&mut scope, &[]).unwrap();
+        assert_eq!(value.into_number(), Some(expected as f64));
+    }
+    let counter = Value::from_json(&mut scope, serde_json::json!({ "counter": 10 }))
+        .unwrap()
+        .into_object()
+        .unwrap()
+        .get(&mut scope, "counter")
+        .unwrap();
+    let value = increment.call(&mut scope, &[counter]).unwrap();
+    assert_eq!(value.into_number(), Some(4.0));
+    info!("global state survived between calls");
+}
@@ -0,0 +1,35 @@
+use tracing::{error, info, Level};
+use unijs::{JsError, Runtime};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let mut runtime = Runtime::with_heap_limits(0, 16 * 1024 * 1024);
+    let result = runtime.load(
+        r#"
+        const hoard = [];
+        for (;;) {
+            hoard.push(new Array(1024).fill("leak"));
+        }
+    "#,
+    );
+    match result {
+        Err(JsError::OutOfMemory) => info!("the script ran out of memory"),
+        Err(err) => error!("unexpected error: {}", err),
+        Ok(_) => error!("the script finished"),
+    }
+
+    // the runtime is still usable
+    let module = runtime.load("exports.answer = 42;").unwrap();
+    let mut scope = runtime.scope(module.context());
+    let answer = module
+        .exports()
+        .clone()
+        .into_object()
+        .unwrap()
+        .get(&mut scope, "answer")
+        .unwrap();
+    info!("answer: {:?}", answer);
+}
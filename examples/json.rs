@@ -19,8 +19,9 @@ async fn main() {
             return person;
         }
     "#;
-    let (mut scope, exports) = Module::load(&js);
-    let json = exports.get(&mut scope, "json").into_function().unwrap();
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let json = exports.get(&mut scope, "json").unwrap().into_function().unwrap();
     let person = Value::serialize(
         &mut scope,
         &Person {
@@ -29,7 +30,7 @@ async fn main() {
         },
     )
     .unwrap();
-    let result = json.call(&mut scope, &[person]);
+    let result = json.call(&mut scope, &[person]).unwrap();
     let person = result.deserialize::<Person>(&mut scope);
     info!("{:?}", person);
 }
@@ -0,0 +1,55 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use tracing::{info, Level};
+use unijs::{Map, Module, Set, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.scores = new Map([["alice", 3], ["bob", 5]]);
+        exports.tags = new Set(["red", "green", "red"]);
+        exports.total = function(scores) {
+            let total = 0;
+            for (const [id, score] of scores) {
+                total += id * score;
+            }
+            return total;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+
+    let scores = exports.get(&mut scope, "scores").unwrap().into_map().unwrap();
+    scores
+        .set(&mut scope, Value::String("carol".to_owned()), Value::Number(8.0))
+        .unwrap();
+    info!("len: {}", scores.len(&mut scope));
+    info!(
+        "bob: {:?}",
+        scores.get(&mut scope, Value::String("bob".to_owned())).unwrap()
+    );
+    let scores: BTreeMap<String, u32> = scores.collect(&mut scope).unwrap();
+    info!("scores: {:?}", scores);
+
+    let tags = exports.get(&mut scope, "tags").unwrap().into_set().unwrap();
+    tags.add(&mut scope, Value::String("blue".to_owned())).unwrap();
+    let tags: HashSet<String> = tags.collect(&mut scope).unwrap();
+    info!("tags: {:?}", tags);
+
+    // integer keys stay numbers in a map, unlike in an object
+    let weights = HashMap::from([(2u32, 10u32), (3, 20)]);
+    let weights = Map::from_entries(&mut scope, &weights).unwrap();
+    let total = exports.get(&mut scope, "total").unwrap().into_function().unwrap();
+    info!("total: {:?}", total.call(&mut scope, &[weights.clone().into()]));
+    let weights: HashMap<u32, u32> = Value::Map(weights).deserialize(&mut scope).unwrap();
+    info!("weights: {:?}", weights);
+
+    let primes = Set::from_values(&mut scope, &[2, 3, 5, 7]).unwrap();
+    info!(
+        "has 4: {:?}",
+        primes.has(&mut scope, Value::Number(4.0)).unwrap()
+    );
+}
@@ -0,0 +1,58 @@
+use tracing::{info, Level};
+use unijs::{Module, PropertyAttributes, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.config = { name: "app", retries: 3, 10: "ten" };
+        exports.assign = function(object, key, value) {
+            "use strict";
+            object[key] = value;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+
+    let config = exports.get(&mut scope, "config").unwrap().into_object().unwrap();
+    info!("keys: {:?}", config.keys(&mut scope).unwrap());
+    info!("entries: {:?}", config.entries(&mut scope).unwrap());
+    assert_eq!(config.len(&mut scope).unwrap(), 3);
+
+    // `has` follows the prototype chain, `has_own` doesn't
+    assert!(config.has(&mut scope, "toString").unwrap());
+    assert!(!config.has_own(&mut scope, "toString").unwrap());
+
+    assert!(config.delete(&mut scope, "10").unwrap());
+    assert!(!config.has(&mut scope, "10").unwrap());
+
+    // hidden properties don't show up in the keys
+    let hidden = PropertyAttributes::default();
+    let defined = config
+        .define_property(&mut scope, "secret", Value::Number(42.0), hidden)
+        .unwrap();
+    assert!(defined);
+    assert!(config.has_own(&mut scope, "secret").unwrap());
+    assert_eq!(config.keys(&mut scope).unwrap(), ["name", "retries"]);
+    // and can't be deleted or redefined
+    assert!(!config.delete(&mut scope, "secret").unwrap());
+    let redefined = config
+        .define_property(&mut scope, "secret", Value::Null, PropertyAttributes::all())
+        .unwrap();
+    assert!(!redefined);
+
+    config.freeze(&mut scope).unwrap();
+    assert!(config.is_frozen(&mut scope).unwrap());
+    assert!(config.is_sealed(&mut scope).unwrap());
+    assert!(!config.is_extensible(&mut scope).unwrap());
+    let assign = exports.get(&mut scope, "assign").unwrap().into_function().unwrap();
+    let error = assign
+        .call(
+            &mut scope,
+            &[config.into(), Value::String("name".to_owned()), Value::Null],
+        )
+        .unwrap_err();
+    info!("assigning to a frozen object: {:?}", error);
+}
@@ -0,0 +1,44 @@
+use tracing::{info, Level};
+use unijs::{Module, PromiseState, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.fetchUser = async function(id) {
+            await null;
+            if (id < 0) {
+                throw new Error("no such user");
+            }
+            return { id, name: "Alice" };
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let fetch_user = exports
+        .get(&mut scope, "fetchUser")
+        .unwrap()
+        .into_function()
+        .unwrap();
+
+    let promise = fetch_user
+        .call(&mut scope, &[Value::Number(1.0)])
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    let user = promise.into_future(&mut scope).await.unwrap();
+    info!("{:?}", user.into_json(&mut scope).unwrap());
+
+    let promise = fetch_user
+        .call(&mut scope, &[Value::Number(-1.0)])
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    if let PromiseState::Pending = promise.state(&mut scope) {
+        info!("waiting for the promise to settle");
+    }
+    let err = promise.into_future(&mut scope).await.unwrap_err();
+    info!("{}", err);
+}
@@ -0,0 +1,60 @@
+use tracing::{info, Level};
+use unijs::{Runtime, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let mut runtime = Runtime::new();
+    let store = runtime
+        .load(
+            r#"
+            exports.create = function(name) {
+                return { name, items: [] };
+            }
+        "#,
+        )
+        .unwrap();
+    let cart = runtime
+        .load(
+            r#"
+            exports.add = function(store, item) {
+                store.items.push(item);
+                return store.items.length;
+            }
+        "#,
+        )
+        .unwrap();
+
+    let mut scope = runtime.scope(store.context());
+    let create = store
+        .exports()
+        .clone()
+        .into_object()
+        .unwrap()
+        .get(&mut scope, "create")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let add = cart
+        .exports()
+        .clone()
+        .into_object()
+        .unwrap()
+        .get(&mut scope, "add")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let shop = create
+        .call(&mut scope, &[Value::String("corner shop".to_owned())])
+        .unwrap();
+    let count = add
+        .call(&mut scope, &[shop, Value::String("apple".to_owned())])
+        .unwrap();
+    info!("items in the shop: {:?}", count);
+    drop(scope);
+
+    drop(store);
+    drop(cart);
+}
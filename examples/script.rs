@@ -0,0 +1,39 @@
+use tracing::{info, Level};
+use unijs::{Runtime, Script};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let source = r#"
+        globalThis.counter = (globalThis.counter || 0) + 1;
+        counter;
+    "#;
+
+    // compile once and run in several contexts
+    let mut runtime = Runtime::new();
+    let first = runtime.context();
+    let second = runtime.context();
+    let cache = {
+        let mut scope = runtime.scope(&first);
+        let script = Script::compile(&mut scope, "counter.js", source).unwrap();
+        info!("first context: {:?}", script.run(&mut scope).unwrap());
+        info!("first context: {:?}", script.run(&mut scope).unwrap());
+        drop(scope);
+        let mut scope = runtime.scope(&second);
+        info!("second context: {:?}", script.run(&mut scope).unwrap());
+        script.code_cache(&mut scope)
+    };
+
+    // a new runtime, as after a restart, can skip parsing with the cache
+    if let Some(cache) = cache {
+        info!("code cache size: {} bytes", cache.len());
+        let mut runtime = Runtime::new();
+        let context = runtime.context();
+        let mut scope = runtime.scope(&context);
+        let script = Script::compile_with_cache(&mut scope, "counter.js", source, &cache).unwrap();
+        info!("cache rejected: {}", script.cache_rejected());
+        info!("new runtime: {:?}", script.run(&mut scope).unwrap());
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, Level};
+use unijs::{Function, Module, SerializeOptions, Value};
+
+#[derive(Deserialize)]
+struct Plugin {
+    name: String,
+    version: Option<String>,
+    run: Function,
+}
+
+#[derive(Serialize)]
+struct Context {
+    user: String,
+    // javascript values pass through serde as is
+    extra: Value,
+    levels: HashMap<u32, String>,
+}
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.plugin = {
+            name: "greeter",
+            version: undefined,
+            run(context) {
+                const levels = context.levels instanceof Map ? "map" : "object";
+                return `hello ${context.user}, extra is ${context.extra}, levels are a ${levels}`;
+            },
+        };
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+
+    let plugin: Plugin = exports
+        .get(&mut scope, "plugin")
+        .unwrap()
+        .deserialize(&mut scope)
+        .unwrap();
+    info!("{} {:?}", plugin.name, plugin.version);
+
+    let context = Context {
+        user: "jabu".to_owned(),
+        extra: Value::Undefined,
+        levels: HashMap::from([(1, "debug".to_owned()), (2, "info".to_owned())]),
+    };
+    let options = SerializeOptions {
+        non_string_keys_as_map: true,
+        ..Default::default()
+    };
+    let context = Value::serialize_with_options(&mut scope, &context, &options).unwrap();
+    info!("{:?}", plugin.run.call(&mut scope, &[context]).unwrap());
+}
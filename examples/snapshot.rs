@@ -0,0 +1,37 @@
+use tracing::{info, Level};
+use unijs::{Runtime, RuntimeOptions, Snapshot, SnapshotBuilder};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let snapshot = SnapshotBuilder::new()
+        .with(
+            "greetings.js",
+            r#"
+            const greetings = { en: "hello", fr: "bonjour", es: "hola" };
+            function greet(lang, name) {
+                return `${greetings[lang]} ${name}`;
+            }
+        "#,
+        )
+        .build()
+        .unwrap();
+    info!("snapshot size: {} bytes", snapshot.as_bytes().len());
+
+    // snapshots can be stored and loaded later
+    let snapshot = Snapshot::from_bytes(snapshot.into_bytes());
+
+    let mut runtime = Runtime::from_snapshot(&snapshot, &RuntimeOptions::default()).unwrap();
+    let module = runtime.load(r#"exports.message = greet("fr", "unijs");"#).unwrap();
+    let mut scope = runtime.scope(module.context());
+    let message = module
+        .exports()
+        .clone()
+        .into_object()
+        .unwrap()
+        .get(&mut scope, "message")
+        .unwrap();
+    info!("message: {:?}", message);
+}
@@ -0,0 +1,43 @@
+use tracing::{error, Level};
+use unijs::{LoadOptions, Module, SourceMap, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    // compiled from src/math.ts, which starts with two lines of type declarations
+    let js = "exports.divide = function (a, b) {
+    if (b === 0) {
+        throw new RangeError(\"division by zero\");
+    }
+    return a / b;
+};";
+    let source_map = SourceMap::parse(
+        r#"{
+            "version": 3,
+            "file": "math.js",
+            "sources": ["src/math.ts"],
+            "names": [],
+            "mappings": "AAEA;AACA;AACA;AACA;AACA;AACA"
+        }"#,
+    )
+    .unwrap();
+
+    let options = LoadOptions {
+        source_map: Some(source_map),
+        ..LoadOptions::new("math.js")
+    };
+    let (mut scope, exports) = Module::load_with_options(js, &options).unwrap();
+    let exports = exports.into_object().unwrap();
+    let divide = exports.get(&mut scope, "divide").unwrap().into_function().unwrap();
+    if let Err(err) = divide.call(&mut scope, &[Value::Number(1.0), Value::Number(0.0)]) {
+        // the stack points at src/math.ts:5
+        error!("{}\n{}", err, err.stack().unwrap_or_default());
+    }
+
+    // errors point at the named script
+    if let Err(err) = Module::load_named("broken.js", "exports.broken = null.value;") {
+        error!("{}\n{}", err, err.stack().unwrap_or_default());
+    }
+}
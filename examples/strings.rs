@@ -0,0 +1,62 @@
+use tracing::{info, Level};
+use unijs::{JsString, Module};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.large = "abc".repeat(100000);
+        exports.emoji = "😀🦀".repeat(1000) + "é";
+        exports.lone = "a\uD800b";
+        exports.codes = function(string) {
+            return Array.from({ length: string.length }, (_, i) => string.charCodeAt(i));
+        }
+        exports.echo = function(string) {
+            return string;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+
+    // strings of any length are converted whole
+    let large = exports.get(&mut scope, "large").unwrap().into_string().unwrap();
+    assert_eq!(large.len(), 300000);
+    assert!(large.ends_with("abcabc"));
+    info!("large: {} bytes", large.len());
+
+    // characters outside the basic multilingual plane are surrogate pairs in
+    // javascript, and stay intact
+    let emoji = exports.get(&mut scope, "emoji").unwrap().into_js_string().unwrap();
+    assert_eq!(emoji.chars().count(), 2001);
+    assert_eq!(emoji.len_utf16(), 4001);
+    assert!(emoji.is_well_formed());
+    assert!(emoji.starts_with("😀🦀😀"));
+    info!("emoji: {} chars", emoji.chars().count());
+
+    // lone surrogates can't be UTF-8, but their code units are kept
+    let lone = exports.get(&mut scope, "lone").unwrap().into_js_string().unwrap();
+    assert!(!lone.is_well_formed());
+    assert_eq!(lone.as_str(), "a\u{FFFD}b");
+    assert_eq!(lone.to_utf16(), [0x61, 0xD800, 0x62]);
+    info!("lone: {:?} {:?}", lone, lone.to_utf16());
+
+    let echo = exports.get(&mut scope, "echo").unwrap().into_function().unwrap();
+    let echoed = echo
+        .call(&mut scope, &[lone.clone().into()])
+        .unwrap()
+        .into_js_string()
+        .unwrap();
+    assert_eq!(echoed, lone);
+
+    let codes = exports.get(&mut scope, "codes").unwrap().into_function().unwrap();
+    let string = JsString::from_utf16(vec![0xDC00, 0x41]);
+    let codes = codes
+        .call(&mut scope, &[string.into()])
+        .unwrap()
+        .deserialize::<Vec<u16>>(&mut scope)
+        .unwrap();
+    assert_eq!(codes, [0xDC00, 0x41]);
+    info!("codes: {:?}", codes);
+}
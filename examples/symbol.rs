@@ -0,0 +1,59 @@
+use tracing::{info, Level};
+use unijs::{Module, Object, PropertyKey, Symbol, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        const secret = Symbol("secret");
+        exports.secret = secret;
+        exports.vault = { name: "vault", [secret]: 42, [Symbol.for("app.id")]: "abc" };
+        exports.count = function* () {
+            yield 1;
+            yield 2;
+            yield 3;
+        }
+        exports.range = function(object) {
+            return [...object];
+        }
+        exports.tag = function(object) {
+            return Object.prototype.toString.call(object);
+        }
+    "#;
+    let (mut scope, exports) = Module::load(&js).unwrap();
+    let exports = exports.into_object().unwrap();
+
+    let secret = exports.get(&mut scope, "secret").unwrap().into_symbol().unwrap();
+    info!("description: {:?}", secret.description(&mut scope));
+
+    let vault = exports.get(&mut scope, "vault").unwrap().into_object().unwrap();
+    info!("secret: {:?}", vault.get(&mut scope, &secret).unwrap());
+    let id = Symbol::for_key(&mut scope, "app.id");
+    info!("app.id: {:?}", vault.get(&mut scope, id).unwrap());
+    info!("keys: {:?}", vault.keys(&mut scope).unwrap());
+    for key in vault.property_keys(&mut scope).unwrap() {
+        match key {
+            PropertyKey::String(key) => info!("string key: {}", key),
+            PropertyKey::Symbol(key) => {
+                info!("symbol key: {:?}", key.description(&mut scope))
+            }
+        }
+    }
+
+    // make an object iterable from rust
+    let object = Object::new(&mut scope);
+    let iterator = exports.get(&mut scope, "count").unwrap();
+    let iterator_symbol = Symbol::iterator(&mut scope);
+    object.set(&mut scope, iterator_symbol, iterator).unwrap();
+    let tag = Symbol::to_string_tag(&mut scope);
+    object
+        .set(&mut scope, tag, Value::String("Counter".to_owned()))
+        .unwrap();
+    let range = exports.get(&mut scope, "range").unwrap().into_function().unwrap();
+    let items = range.call(&mut scope, &[object.clone().into()]).unwrap();
+    info!("spread: {:?}", items.deserialize::<Vec<u32>>(&mut scope));
+    let tag = exports.get(&mut scope, "tag").unwrap().into_function().unwrap();
+    info!("tag: {:?}", tag.call(&mut scope, &[object.into()]).unwrap());
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use tracing::{error, info, Level};
+use unijs::{JsError, Runtime, Value};
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let mut runtime = Runtime::new();
+    let module = runtime
+        .load(
+            r#"
+            exports.spin = function() {
+                for (;;) {}
+            }
+            exports.add = function(a, b) {
+                return a + b;
+            }
+        "#,
+        )
+        .unwrap();
+
+    let handle = runtime.termination_handle();
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let spin = exports.get(&mut scope, "spin").unwrap().into_function().unwrap();
+    let add = exports.get(&mut scope, "add").unwrap().into_function().unwrap();
+
+    match spin.call_with_timeout(&mut scope, &[], Duration::from_millis(100)) {
+        Err(JsError::Terminated) => info!("spin was terminated after 100ms"),
+        result => error!("unexpected result: {:?}", result),
+    }
+
+    // terminate from another thread
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        handle.terminate();
+    });
+    match spin.call(&mut scope, &[]) {
+        Err(JsError::Terminated) => info!("spin was terminated from another thread"),
+        result => error!("unexpected result: {:?}", result),
+    }
+
+    // the runtime is still usable
+    let sum = add
+        .call(&mut scope, &[Value::Number(1.0), Value::Number(2.0)])
+        .unwrap();
+    info!("sum: {:?}", sum);
+}
@@ -0,0 +1,33 @@
+use tracing::{info, Level};
+use unijs::Runtime;
+
+#[tokasm::main]
+async fn main() {
+    unilog::init(Level::INFO, "");
+    unijs::init();
+
+    let js = r#"
+        exports.log = [];
+        let ticks = 0;
+        const interval = setInterval(() => {
+            exports.log.push(`tick ${++ticks}`);
+            if (ticks === 3) {
+                clearInterval(interval);
+            }
+        }, 10);
+        setTimeout(name => exports.log.push(`hello ${name}`), 25, "timers");
+        Promise.resolve().then(() => exports.log.push("promise"));
+        queueMicrotask(() => exports.log.push("microtask"));
+        exports.log.push("script");
+    "#;
+    let mut runtime = Runtime::new();
+    let module = runtime.load(js).unwrap();
+    info!("pending work: {}", runtime.has_pending_work());
+    runtime.run_event_loop().await.unwrap();
+    info!("pending work: {}", runtime.has_pending_work());
+
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let log = exports.get(&mut scope, "log").unwrap();
+    info!("log: {:?}", log.into_json(&mut scope).unwrap());
+}
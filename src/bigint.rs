@@ -0,0 +1,215 @@
+use crate::Value;
+
+/// An arbitrary-precision JavaScript `BigInt`, stored as a sign and a
+/// magnitude of 64-bit words, least significant first.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    words: Vec<u64>,
+}
+
+impl BigInt {
+    pub fn from_words(negative: bool, words: Vec<u64>) -> Self {
+        let mut bigint = Self { negative, words };
+        while bigint.words.last() == Some(&0) {
+            bigint.words.pop();
+        }
+        // there is no negative zero
+        bigint.negative &= !bigint.words.is_empty();
+        bigint
+    }
+
+    /// Creates a `BigInt` from a little endian magnitude of any length.
+    pub fn from_bytes_le(negative: bool, bytes: &[u8]) -> Self {
+        let words = bytes
+            .chunks(8)
+            .map(|chunk| {
+                let mut word = [0; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(word)
+            })
+            .collect();
+        Self::from_words(negative, words)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The magnitude, least significant word first, without trailing zeros.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// The little endian magnitude, without trailing zeros.
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self
+            .words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect::<Vec<_>>();
+        while bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        bytes
+    }
+
+    fn magnitude(&self) -> Option<u128> {
+        match self.words[..] {
+            [] => Some(0),
+            [low] => Some(low as u128),
+            [low, high] => Some(((high as u128) << 64) | low as u128),
+            _ => None,
+        }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_i128().and_then(|value| value.try_into().ok())
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|value| value.try_into().ok())
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            magnitude.try_into().ok()
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.negative {
+            None
+        } else {
+            self.magnitude()
+        }
+    }
+
+    /// The nearest number, which loses precision beyond 2^53.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .words
+            .iter()
+            .rev()
+            .fold(0.0, |value, word| value * 18446744073709551616.0 + *word as f64);
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8(bigint: v8::Local<v8::BigInt>) -> Self {
+        let mut words = vec![0; bigint.word_count()];
+        let (negative, _) = bigint.to_words_array(&mut words);
+        Self::from_words(negative, words)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(&self, scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::BigInt> {
+        v8::BigInt::new_from_words(scope, self.negative, &self.words).unwrap()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(bigint: js_sys::BigInt) -> Self {
+        let hex = String::from(bigint.to_string(16).unwrap());
+        let (negative, hex) = match hex.strip_prefix('-') {
+            Some(hex) => (true, hex),
+            None => (false, hex.as_str()),
+        };
+        // parse 16 digits, one word, at a time from the least significant end
+        let words = hex
+            .as_bytes()
+            .rchunks(16)
+            .map(|digits| u64::from_str_radix(std::str::from_utf8(digits).unwrap(), 16).unwrap())
+            .collect();
+        Self::from_words(negative, words)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> js_sys::BigInt {
+        let mut hex = String::from("0x0");
+        for word in self.words.iter().rev() {
+            hex.push_str(&format!("{:016x}", word));
+        }
+        let value = js_sys::BigInt::new(&wasm_bindgen::JsValue::from_str(&hex)).unwrap();
+        if self.negative {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+impl std::fmt::Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        if self.words.is_empty() {
+            return f.write_str("0");
+        }
+        // repeatedly divide by the largest power of ten that fits in a word
+        let mut words = self.words.clone();
+        let mut chunks = vec![];
+        while !words.is_empty() {
+            let mut remainder = 0u128;
+            for word in words.iter_mut().rev() {
+                let value = (remainder << 64) | *word as u128;
+                *word = (value / CHUNK as u128) as u64;
+                remainder = value % CHUNK as u128;
+            }
+            chunks.push(remainder as u64);
+            while words.last() == Some(&0) {
+                words.pop();
+            }
+        }
+        if self.negative {
+            f.write_str("-")?;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:019}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}n", self)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        Self::from(value as i128)
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(value: u64) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let magnitude = value.unsigned_abs();
+        Self::from_words(value < 0, vec![magnitude as u64, (magnitude >> 64) as u64])
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        Self::from_words(false, vec![value as u64, (value >> 64) as u64])
+    }
+}
+
+impl From<BigInt> for Value {
+    fn from(value: BigInt) -> Self {
+        Value::BigInt(value)
+    }
+}
@@ -0,0 +1,558 @@
+use crate::{JsError, Scope, Value};
+
+/// The element type of a [`TypedArray`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypedArrayKind {
+    Int8,
+    Uint8,
+    Uint8Clamped,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+    BigInt64,
+    BigUint64,
+}
+
+impl TypedArrayKind {
+    /// The size of each element, in bytes.
+    pub fn element_size(self) -> usize {
+        match self {
+            Self::Int8 | Self::Uint8 | Self::Uint8Clamped => 1,
+            Self::Int16 | Self::Uint16 => 2,
+            Self::Int32 | Self::Uint32 | Self::Float32 => 4,
+            Self::Float64 | Self::BigInt64 | Self::BigUint64 => 8,
+        }
+    }
+
+    /// The name of the JavaScript constructor, such as `Uint8Array`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Int8 => "Int8Array",
+            Self::Uint8 => "Uint8Array",
+            Self::Uint8Clamped => "Uint8ClampedArray",
+            Self::Int16 => "Int16Array",
+            Self::Uint16 => "Uint16Array",
+            Self::Int32 => "Int32Array",
+            Self::Uint32 => "Uint32Array",
+            Self::Float32 => "Float32Array",
+            Self::Float64 => "Float64Array",
+            Self::BigInt64 => "BigInt64Array",
+            Self::BigUint64 => "BigUint64Array",
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Int8,
+            Self::Uint8,
+            Self::Uint8Clamped,
+            Self::Int16,
+            Self::Uint16,
+            Self::Int32,
+            Self::Uint32,
+            Self::Float32,
+            Self::Float64,
+            Self::BigInt64,
+            Self::BigUint64,
+        ]
+        .into_iter()
+        .find(|kind| kind.name() == name)
+    }
+
+    /// Decodes elements from their in-memory representation, which is little
+    /// endian on every supported platform.
+    fn to_json(self, bytes: &[u8]) -> Vec<serde_json::Value> {
+        bytes
+            .chunks_exact(self.element_size())
+            .map(|bytes| {
+                let number = |value: f64| {
+                    serde_json::Number::from_f64(value)
+                        .map(serde_json::Value::Number)
+                        .unwrap_or(serde_json::Value::Null)
+                };
+                match self {
+                    Self::Int8 => (bytes[0] as i8).into(),
+                    Self::Uint8 | Self::Uint8Clamped => bytes[0].into(),
+                    Self::Int16 => i16::from_le_bytes(bytes.try_into().unwrap()).into(),
+                    Self::Uint16 => u16::from_le_bytes(bytes.try_into().unwrap()).into(),
+                    Self::Int32 => i32::from_le_bytes(bytes.try_into().unwrap()).into(),
+                    Self::Uint32 => u32::from_le_bytes(bytes.try_into().unwrap()).into(),
+                    Self::Float32 => number(f32::from_le_bytes(bytes.try_into().unwrap()) as f64),
+                    Self::Float64 => number(f64::from_le_bytes(bytes.try_into().unwrap())),
+                    Self::BigInt64 => i64::from_le_bytes(bytes.try_into().unwrap()).into(),
+                    Self::BigUint64 => u64::from_le_bytes(bytes.try_into().unwrap()).into(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A JavaScript `ArrayBuffer`. Its bytes are accessed in place through
+/// closures, which can't call back into JavaScript while the bytes are
+/// borrowed.
+#[derive(Clone)]
+pub struct ArrayBuffer {
+    #[cfg(not(target_arch = "wasm32"))]
+    buffer: v8::Global<v8::ArrayBuffer>,
+    #[cfg(target_arch = "wasm32")]
+    buffer: js_sys::ArrayBuffer,
+}
+
+impl ArrayBuffer {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        buffer: v8::Local<'b, v8::ArrayBuffer>,
+    ) -> Self {
+        Self {
+            buffer: v8::Global::new(scope, buffer),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+    ) -> v8::Local<'s, v8::ArrayBuffer> {
+        v8::Local::new(scope, &self.buffer)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(buffer: js_sys::ArrayBuffer) -> Self {
+        Self { buffer }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> js_sys::ArrayBuffer {
+        self.buffer.clone()
+    }
+
+    /// Creates a zero-filled buffer.
+    #[allow(unused_variables)]
+    pub fn new(scope: &mut Scope, byte_length: usize) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let buffer = v8::ArrayBuffer::new(scope, byte_length);
+                Self::from_v8(scope, buffer)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::from_web(js_sys::ArrayBuffer::new(byte_length as u32))
+        }
+    }
+
+    /// Creates a buffer that takes ownership of `bytes`. On the web, the bytes
+    /// are copied into the browser's memory.
+    #[allow(unused_variables)]
+    pub fn from_vec(scope: &mut Scope, bytes: Vec<u8>) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let store = v8::ArrayBuffer::new_backing_store_from_vec(bytes).make_shared();
+            scope.enter(|scope| {
+                let buffer = v8::ArrayBuffer::with_backing_store(scope, &store);
+                Self::from_v8(scope, buffer)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::from_web(js_sys::Uint8Array::from(bytes.as_slice()).buffer())
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn byte_length(&self, scope: &mut Scope) -> usize {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| self.to_v8(scope).byte_length())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.buffer.byte_length() as usize
+        }
+    }
+
+    /// Calls `f` with the buffer's bytes. On the web, `f` sees a copy.
+    #[allow(unused_variables)]
+    pub fn with_bytes<R>(&self, scope: &mut Scope, f: impl FnOnce(&[u8]) -> R) -> R {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let buffer = self.to_v8(scope);
+                let store = buffer.get_backing_store();
+                f(bytes(&store, 0, buffer.byte_length()))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            f(&js_sys::Uint8Array::new(&self.buffer).to_vec())
+        }
+    }
+
+    /// Calls `f` with the buffer's bytes, which it may modify. On the web, `f`
+    /// modifies a copy, which is written back once it returns.
+    #[allow(unused_variables)]
+    pub fn with_bytes_mut<R>(&self, scope: &mut Scope, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let buffer = self.to_v8(scope);
+                let store = buffer.get_backing_store();
+                f(bytes_mut(&store, 0, buffer.byte_length()))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let view = js_sys::Uint8Array::new(&self.buffer);
+            let mut bytes = view.to_vec();
+            let result = f(&mut bytes);
+            view.copy_from(&bytes);
+            result
+        }
+    }
+
+    /// Copies the buffer's bytes.
+    pub fn to_vec(&self, scope: &mut Scope) -> Vec<u8> {
+        self.with_bytes(scope, |bytes| bytes.to_vec())
+    }
+}
+
+impl std::fmt::Debug for ArrayBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[array buffer]")
+    }
+}
+
+impl From<ArrayBuffer> for Value {
+    fn from(value: ArrayBuffer) -> Self {
+        Value::ArrayBuffer(value)
+    }
+}
+
+/// A view of an [`ArrayBuffer`] as an array of numbers, such as a
+/// `Uint8Array`.
+#[derive(Clone)]
+pub struct TypedArray {
+    #[cfg(not(target_arch = "wasm32"))]
+    array: v8::Global<v8::TypedArray>,
+    #[cfg(target_arch = "wasm32")]
+    array: js_sys::Object,
+    kind: TypedArrayKind,
+}
+
+impl TypedArray {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        array: v8::Local<'b, v8::TypedArray>,
+    ) -> Self {
+        let kind = if array.is_int8_array() {
+            TypedArrayKind::Int8
+        } else if array.is_uint8_array() {
+            TypedArrayKind::Uint8
+        } else if array.is_uint8_clamped_array() {
+            TypedArrayKind::Uint8Clamped
+        } else if array.is_int16_array() {
+            TypedArrayKind::Int16
+        } else if array.is_uint16_array() {
+            TypedArrayKind::Uint16
+        } else if array.is_int32_array() {
+            TypedArrayKind::Int32
+        } else if array.is_uint32_array() {
+            TypedArrayKind::Uint32
+        } else if array.is_float32_array() {
+            TypedArrayKind::Float32
+        } else if array.is_float64_array() {
+            TypedArrayKind::Float64
+        } else if array.is_big_int64_array() {
+            TypedArrayKind::BigInt64
+        } else {
+            TypedArrayKind::BigUint64
+        };
+        Self {
+            array: v8::Global::new(scope, array),
+            kind,
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(&self, scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::TypedArray> {
+        v8::Local::new(scope, &self.array)
+    }
+
+    /// Returns `None` for typed arrays this crate doesn't know about.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(array: js_sys::Object) -> Option<Self> {
+        let tag = js_sys::Reflect::get(&array, &js_sys::Symbol::to_string_tag()).ok()?;
+        let kind = TypedArrayKind::from_name(&tag.as_string()?)?;
+        Some(Self { array, kind })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> js_sys::Object {
+        self.array.clone()
+    }
+
+    /// Creates a `Uint8Array` that takes ownership of `bytes`. On the web, the
+    /// bytes are copied into the browser's memory.
+    pub fn from_vec(scope: &mut Scope, bytes: Vec<u8>) -> Self {
+        let length = bytes.len();
+        let buffer = ArrayBuffer::from_vec(scope, bytes);
+        Self::new(scope, TypedArrayKind::Uint8, &buffer, 0, length).unwrap()
+    }
+
+    /// Creates a view of `length` elements of `buffer`, starting `byte_offset`
+    /// bytes in.
+    #[allow(unused_variables)]
+    pub fn new(
+        scope: &mut Scope,
+        kind: TypedArrayKind,
+        buffer: &ArrayBuffer,
+        byte_offset: usize,
+        length: usize,
+    ) -> Result<Self, JsError> {
+        let byte_length = buffer.byte_length(scope);
+        if byte_offset % kind.element_size() != 0 {
+            return Err(JsError::new(
+                "RangeError",
+                &format!(
+                    "start offset of {} should be a multiple of {}",
+                    kind.name(),
+                    kind.element_size()
+                ),
+            ));
+        }
+        if length
+            .checked_mul(kind.element_size())
+            .and_then(|size| size.checked_add(byte_offset))
+            .map_or(true, |end| end > byte_length)
+        {
+            return Err(JsError::new(
+                "RangeError",
+                &format!("invalid {} length: {}", kind.name(), length),
+            ));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.try_enter(|scope| {
+                let buffer = buffer.to_v8(scope);
+                let array: v8::Local<v8::TypedArray> = match kind {
+                    TypedArrayKind::Int8 => {
+                        v8::Int8Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::Uint8 => {
+                        v8::Uint8Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::Uint8Clamped => {
+                        v8::Uint8ClampedArray::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::Int16 => {
+                        v8::Int16Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::Uint16 => {
+                        v8::Uint16Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::Int32 => {
+                        v8::Int32Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::Uint32 => {
+                        v8::Uint32Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::Float32 => {
+                        v8::Float32Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::Float64 => {
+                        v8::Float64Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::BigInt64 => {
+                        v8::BigInt64Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                    TypedArrayKind::BigUint64 => {
+                        v8::BigUint64Array::new(scope, buffer, byte_offset, length)?.into()
+                    }
+                };
+                Some(Self {
+                    array: v8::Global::new(scope, array),
+                    kind,
+                })
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use wasm_bindgen::{JsCast, JsValue};
+            let constructor = js_sys::Reflect::get(&js_sys::global(), &kind.name().into())
+                .map_err(JsError::from_web)?;
+            let args = js_sys::Array::of3(
+                &buffer.to_web(),
+                &JsValue::from(byte_offset as f64),
+                &JsValue::from(length as f64),
+            );
+            let array = js_sys::Reflect::construct(constructor.unchecked_ref(), &args)
+                .map_err(JsError::from_web)?;
+            Ok(Self {
+                array: array.unchecked_into(),
+                kind,
+            })
+        }
+    }
+
+    pub fn kind(&self) -> TypedArrayKind {
+        self.kind
+    }
+
+    /// The buffer this array views.
+    #[allow(unused_variables)]
+    pub fn buffer(&self, scope: &mut Scope) -> ArrayBuffer {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let buffer = self.to_v8(scope).buffer(scope).unwrap();
+                ArrayBuffer::from_v8(scope, buffer)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use wasm_bindgen::JsCast;
+            let buffer = js_sys::Reflect::get(&self.array, &"buffer".into()).unwrap();
+            ArrayBuffer::from_web(buffer.unchecked_into())
+        }
+    }
+
+    /// The offset of the first element into the buffer, in bytes.
+    #[allow(unused_variables)]
+    pub fn byte_offset(&self, scope: &mut Scope) -> usize {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| self.to_v8(scope).byte_offset())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.number_property("byteOffset")
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn byte_length(&self, scope: &mut Scope) -> usize {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| self.to_v8(scope).byte_length())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.number_property("byteLength")
+        }
+    }
+
+    /// The number of elements.
+    pub fn length(&self, scope: &mut Scope) -> usize {
+        self.byte_length(scope) / self.kind.element_size()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn number_property(&self, name: &str) -> usize {
+        js_sys::Reflect::get(&self.array, &name.into())
+            .ok()
+            .and_then(|value| value.as_f64())
+            .unwrap_or_default() as usize
+    }
+
+    /// Calls `f` with the bytes this array views. On the web, `f` sees a copy.
+    #[allow(unused_variables)]
+    pub fn with_bytes<R>(&self, scope: &mut Scope, f: impl FnOnce(&[u8]) -> R) -> R {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let array = self.to_v8(scope);
+                let store = array.buffer(scope).unwrap().get_backing_store();
+                f(bytes(&store, array.byte_offset(), array.byte_length()))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            f(&self.byte_view(scope).to_vec())
+        }
+    }
+
+    /// Calls `f` with the bytes this array views, which it may modify. On the
+    /// web, `f` modifies a copy, which is written back once it returns.
+    #[allow(unused_variables)]
+    pub fn with_bytes_mut<R>(&self, scope: &mut Scope, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let array = self.to_v8(scope);
+                let store = array.buffer(scope).unwrap().get_backing_store();
+                f(bytes_mut(&store, array.byte_offset(), array.byte_length()))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let view = self.byte_view(scope);
+            let mut bytes = view.to_vec();
+            let result = f(&mut bytes);
+            view.copy_from(&bytes);
+            result
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn byte_view(&self, scope: &mut Scope) -> js_sys::Uint8Array {
+        js_sys::Uint8Array::new_with_byte_offset_and_length(
+            &self.buffer(scope).to_web(),
+            self.byte_offset(scope) as u32,
+            self.byte_length(scope) as u32,
+        )
+    }
+
+    /// Copies the bytes this array views.
+    pub fn to_vec(&self, scope: &mut Scope) -> Vec<u8> {
+        self.with_bytes(scope, |bytes| bytes.to_vec())
+    }
+
+    /// Copies the elements into JSON numbers.
+    pub(crate) fn to_json(&self, scope: &mut Scope) -> Vec<serde_json::Value> {
+        let kind = self.kind;
+        self.with_bytes(scope, |bytes| kind.to_json(bytes))
+    }
+}
+
+impl std::fmt::Debug for TypedArray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}]", self.kind.name())
+    }
+}
+
+impl From<TypedArray> for Value {
+    fn from(value: TypedArray) -> Self {
+        Value::TypedArray(value)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn bytes(store: &v8::SharedRef<v8::BackingStore>, offset: usize, length: usize) -> &[u8] {
+    match store.data() {
+        // the store outlives the slice, and nothing can run JavaScript, which
+        // could detach or resize it, while the slice is borrowed
+        Some(data) => unsafe {
+            std::slice::from_raw_parts((data.as_ptr() as *const u8).add(offset), length)
+        },
+        None => &[],
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::mut_from_ref)]
+fn bytes_mut(store: &v8::SharedRef<v8::BackingStore>, offset: usize, length: usize) -> &mut [u8] {
+    match store.data() {
+        // see `bytes`; the slice is also the only reference to these bytes
+        Some(data) => unsafe {
+            std::slice::from_raw_parts_mut((data.as_ptr() as *mut u8).add(offset), length)
+        },
+        None => &mut [],
+    }
+}
@@ -0,0 +1,450 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{JsError, Scope, SerdeError, Value};
+
+/// A JavaScript `Map`, whose keys can be any value and keep their insertion
+/// order.
+#[derive(Clone)]
+pub struct Map {
+    #[cfg(not(target_arch = "wasm32"))]
+    map: v8::Global<v8::Map>,
+    #[cfg(target_arch = "wasm32")]
+    map: js_sys::Map,
+}
+
+impl Map {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        map: v8::Local<'b, v8::Map>,
+    ) -> Self {
+        Self {
+            map: v8::Global::new(scope, map),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(&self, scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::Map> {
+        v8::Local::new(scope, &self.map)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(map: js_sys::Map) -> Self {
+        Self { map }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> js_sys::Map {
+        self.map.clone()
+    }
+
+    #[allow(unused_variables)]
+    pub fn new(scope: &mut Scope) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let map = scope.enter(|scope| {
+                let map = v8::Map::new(scope);
+                v8::Global::new(scope, map)
+            });
+            Self { map }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                map: js_sys::Map::new(),
+            }
+        }
+    }
+
+    /// Creates a map from Rust entries, such as a `HashMap` or `BTreeMap`.
+    /// Keys keep their type, so integer keys stay numbers rather than becoming
+    /// strings like in an object.
+    pub fn from_entries<'a, K, V, I>(scope: &mut Scope, entries: I) -> Result<Self, SerdeError>
+    where
+        K: Serialize + 'a,
+        V: Serialize + 'a,
+        I: IntoIterator<Item = (&'a K, &'a V)>,
+    {
+        let map = Self::new(scope);
+        for (key, value) in entries {
+            let key = Value::serialize(scope, key)?;
+            let value = Value::serialize(scope, value)?;
+            map.set(scope, key, value)?;
+        }
+        Ok(map)
+    }
+
+    /// Deserializes every entry into a Rust collection, such as a `HashMap` or
+    /// `BTreeMap`.
+    pub fn collect<K, V, C>(&self, scope: &mut Scope) -> Result<C, SerdeError>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+        C: FromIterator<(K, V)>,
+    {
+        self.entries(scope)?
+            .into_iter()
+            .map(|(key, value)| Ok((key.deserialize(scope)?, value.deserialize(scope)?)))
+            .collect()
+    }
+
+    #[allow(unused_variables)]
+    pub fn len(&self, scope: &mut Scope) -> usize {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let map = self.map.clone();
+            scope.enter(move |scope| v8::Local::new(scope, map).size())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.map.size() as usize
+        }
+    }
+
+    pub fn is_empty(&self, scope: &mut Scope) -> bool {
+        self.len(scope) == 0
+    }
+
+    /// Gets the value for `key`, or `undefined` if there is none.
+    #[allow(unused_variables)]
+    pub fn get(&self, scope: &mut Scope, key: Value) -> Result<Value, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let map = self.map.clone();
+            scope.try_enter(move |scope| {
+                let map = v8::Local::new(scope, map);
+                let key = key.to_v8(scope)?;
+                let value = map.get(scope, key)?;
+                Some(Value::from_v8(scope, value))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(Value::from_web(self.map.get(&key.to_web())))
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn set(&self, scope: &mut Scope, key: Value, value: Value) -> Result<(), JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let map = self.map.clone();
+            scope.try_enter(move |scope| {
+                let map = v8::Local::new(scope, map);
+                let key = key.to_v8(scope)?;
+                let value = value.to_v8(scope)?;
+                map.set(scope, key, value).map(|_| ())
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.map.set(&key.to_web(), &value.to_web());
+            Ok(())
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn has(&self, scope: &mut Scope, key: Value) -> Result<bool, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let map = self.map.clone();
+            scope.try_enter(move |scope| {
+                let map = v8::Local::new(scope, map);
+                let key = key.to_v8(scope)?;
+                map.has(scope, key)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(self.map.has(&key.to_web()))
+        }
+    }
+
+    /// Removes `key`, returning whether it was present.
+    #[allow(unused_variables)]
+    pub fn delete(&self, scope: &mut Scope, key: Value) -> Result<bool, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let map = self.map.clone();
+            scope.try_enter(move |scope| {
+                let map = v8::Local::new(scope, map);
+                let key = key.to_v8(scope)?;
+                map.delete(scope, key)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(self.map.delete(&key.to_web()))
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn clear(&self, scope: &mut Scope) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let map = self.map.clone();
+            scope.enter(move |scope| v8::Local::new(scope, map).clear())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.map.clear()
+        }
+    }
+
+    /// The entries in insertion order.
+    #[allow(unused_variables)]
+    pub fn entries(&self, scope: &mut Scope) -> Result<Vec<(Value, Value)>, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let map = self.map.clone();
+            scope.try_enter(move |scope| {
+                let map = v8::Local::new(scope, map);
+                // keys and values are interleaved
+                let array = map.as_array(scope);
+                let mut entries = vec![];
+                for i in (0..array.length()).step_by(2) {
+                    let key = array.get_index(scope, i)?;
+                    let value = array.get_index(scope, i + 1)?;
+                    entries.push((Value::from_v8(scope, key), Value::from_v8(scope, value)));
+                }
+                Some(entries)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(js_sys::Array::from(&self.map)
+                .iter()
+                .map(|entry| {
+                    let entry = js_sys::Array::from(&entry);
+                    (Value::from_web(entry.get(0)), Value::from_web(entry.get(1)))
+                })
+                .collect())
+        }
+    }
+
+    pub fn keys(&self, scope: &mut Scope) -> Result<Vec<Value>, JsError> {
+        Ok(self.entries(scope)?.into_iter().map(|(key, _)| key).collect())
+    }
+
+    pub fn values(&self, scope: &mut Scope) -> Result<Vec<Value>, JsError> {
+        Ok(self
+            .entries(scope)?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect())
+    }
+}
+
+impl std::fmt::Debug for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[map]")
+    }
+}
+
+impl From<Map> for Value {
+    fn from(value: Map) -> Self {
+        Value::Map(value)
+    }
+}
+
+/// A JavaScript `Set` of unique values, in insertion order.
+#[derive(Clone)]
+pub struct Set {
+    #[cfg(not(target_arch = "wasm32"))]
+    set: v8::Global<v8::Set>,
+    #[cfg(target_arch = "wasm32")]
+    set: js_sys::Set,
+}
+
+impl Set {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        set: v8::Local<'b, v8::Set>,
+    ) -> Self {
+        Self {
+            set: v8::Global::new(scope, set),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(&self, scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::Set> {
+        v8::Local::new(scope, &self.set)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(set: js_sys::Set) -> Self {
+        Self { set }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> js_sys::Set {
+        self.set.clone()
+    }
+
+    #[allow(unused_variables)]
+    pub fn new(scope: &mut Scope) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let set = scope.enter(|scope| {
+                let set = v8::Set::new(scope);
+                v8::Global::new(scope, set)
+            });
+            Self { set }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                set: js_sys::Set::new(&wasm_bindgen::JsValue::undefined()),
+            }
+        }
+    }
+
+    /// Creates a set from Rust values, such as a `HashSet` or `BTreeSet`.
+    pub fn from_values<'a, T, I>(scope: &mut Scope, values: I) -> Result<Self, SerdeError>
+    where
+        T: Serialize + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        let set = Self::new(scope);
+        for value in values {
+            let value = Value::serialize(scope, value)?;
+            set.add(scope, value)?;
+        }
+        Ok(set)
+    }
+
+    /// Deserializes every value into a Rust collection, such as a `HashSet` or
+    /// `BTreeSet`.
+    pub fn collect<T, C>(&self, scope: &mut Scope) -> Result<C, SerdeError>
+    where
+        T: DeserializeOwned,
+        C: FromIterator<T>,
+    {
+        self.values(scope)?
+            .into_iter()
+            .map(|value| value.deserialize(scope))
+            .collect()
+    }
+
+    #[allow(unused_variables)]
+    pub fn len(&self, scope: &mut Scope) -> usize {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let set = self.set.clone();
+            scope.enter(move |scope| v8::Local::new(scope, set).size())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.set.size() as usize
+        }
+    }
+
+    pub fn is_empty(&self, scope: &mut Scope) -> bool {
+        self.len(scope) == 0
+    }
+
+    #[allow(unused_variables)]
+    pub fn add(&self, scope: &mut Scope, value: Value) -> Result<(), JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let set = self.set.clone();
+            scope.try_enter(move |scope| {
+                let set = v8::Local::new(scope, set);
+                let value = value.to_v8(scope)?;
+                set.add(scope, value).map(|_| ())
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.set.add(&value.to_web());
+            Ok(())
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn has(&self, scope: &mut Scope, value: Value) -> Result<bool, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let set = self.set.clone();
+            scope.try_enter(move |scope| {
+                let set = v8::Local::new(scope, set);
+                let value = value.to_v8(scope)?;
+                set.has(scope, value)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(self.set.has(&value.to_web()))
+        }
+    }
+
+    /// Removes `value`, returning whether it was present.
+    #[allow(unused_variables)]
+    pub fn delete(&self, scope: &mut Scope, value: Value) -> Result<bool, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let set = self.set.clone();
+            scope.try_enter(move |scope| {
+                let set = v8::Local::new(scope, set);
+                let value = value.to_v8(scope)?;
+                set.delete(scope, value)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(self.set.delete(&value.to_web()))
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn clear(&self, scope: &mut Scope) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let set = self.set.clone();
+            scope.enter(move |scope| v8::Local::new(scope, set).clear())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.set.clear()
+        }
+    }
+
+    /// The values in insertion order.
+    #[allow(unused_variables)]
+    pub fn values(&self, scope: &mut Scope) -> Result<Vec<Value>, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let set = self.set.clone();
+            scope.try_enter(move |scope| {
+                let set = v8::Local::new(scope, set);
+                let array = set.as_array(scope);
+                let mut values = vec![];
+                for i in 0..array.length() {
+                    let value = array.get_index(scope, i)?;
+                    values.push(Value::from_v8(scope, value));
+                }
+                Some(values)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(js_sys::Array::from(&self.set)
+                .iter()
+                .map(Value::from_web)
+                .collect())
+        }
+    }
+}
+
+impl std::fmt::Debug for Set {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[set]")
+    }
+}
+
+impl From<Set> for Value {
+    fn from(value: Set) -> Self {
+        Value::Set(value)
+    }
+}
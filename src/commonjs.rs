@@ -0,0 +1,125 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{Function, JsError, LoadOptions, ModuleLoader, Object, Scope, Value};
+
+/// The modules loaded through `require`, shared by every `require` function of a
+/// module tree.
+struct ModuleCache {
+    loader: Option<Rc<dyn ModuleLoader>>,
+    modules: RefCell<HashMap<String, Object>>,
+}
+
+const WRAPPER: &str = "(function (module, exports, require, __filename, __dirname) {";
+
+/// Runs `source` as a CommonJS module, returning its `module.exports`.
+pub(crate) fn load(
+    scope: &mut Scope,
+    loader: Option<Rc<dyn ModuleLoader>>,
+    source: &str,
+    options: &LoadOptions,
+) -> Result<Value, JsError> {
+    let cache = Rc::new(ModuleCache {
+        loader,
+        modules: RefCell::new(HashMap::new()),
+    });
+    run(scope, &cache, source, options)
+}
+
+fn run(
+    scope: &mut Scope,
+    cache: &Rc<ModuleCache>,
+    source: &str,
+    options: &LoadOptions,
+) -> Result<Value, JsError> {
+    let name = options.name.as_str();
+    let module = Object::new(scope);
+    let exports = Object::new(scope);
+    module.set(scope, "exports", exports.clone().into())?;
+    cache
+        .modules
+        .borrow_mut()
+        .insert(name.to_owned(), module.clone());
+
+    let result = evaluate(scope, cache, source, options, &module, exports);
+    if result.is_err() {
+        // a failed module isn't cached, so requiring it again retries it and
+        // fails again, like node
+        cache.modules.borrow_mut().remove(name);
+    }
+    result
+}
+
+fn evaluate(
+    scope: &mut Scope,
+    cache: &Rc<ModuleCache>,
+    source: &str,
+    options: &LoadOptions,
+    module: &Object,
+    exports: Object,
+) -> Result<Value, JsError> {
+    let name = options.name.as_str();
+    // the wrapper is kept on the first line so that line numbers are unchanged,
+    // and the column offset makes up for it on the first line
+    let origin = LoadOptions {
+        name: name.to_owned(),
+        line_offset: options.line_offset,
+        column_offset: options.column_offset - WRAPPER.len() as i32,
+        source_map: None,
+    };
+    let wrapper = scope
+        .eval(&format!("{}{}\n}})", WRAPPER, source), &origin)?
+        .into_function()
+        .ok_or_else(|| JsError::new("TypeError", "module wrapper is not a function"))?;
+    let require = require_function(scope, cache.clone(), name.to_owned());
+    wrapper.call(
+        scope,
+        &[
+            module.clone().into(),
+            exports.into(),
+            require.into(),
+            Value::String(name.to_owned()),
+            Value::String(dirname(name).to_owned()),
+        ],
+    )?;
+    module.get(scope, "exports")
+}
+
+fn require_function(scope: &mut Scope, cache: Rc<ModuleCache>, referrer: String) -> Function {
+    Function::new(scope, move |scope, args| {
+        let Some(specifier) = args.get(0).into_string() else {
+            return Err(Value::error(scope, "module specifier must be a string"));
+        };
+        require(scope, &cache, &specifier, &referrer).map_err(|err| err.to_value(scope))
+    })
+}
+
+fn require(
+    scope: &mut Scope,
+    cache: &Rc<ModuleCache>,
+    specifier: &str,
+    referrer: &str,
+) -> Result<Value, JsError> {
+    let Some(loader) = cache.loader.clone() else {
+        return Err(JsError::new(
+            "Error",
+            &format!("cannot find module '{}': no module loader is set", specifier),
+        ));
+    };
+    let name = loader.resolve(specifier, referrer)?;
+    let cached = cache.modules.borrow().get(&name).cloned();
+    if let Some(module) = cached {
+        // modules that are still loading (circular requires) expose their
+        // partially filled exports, like node
+        return module.get(scope, "exports");
+    }
+    let source = loader.load(&name)?;
+    run(scope, cache, &source, &LoadOptions::new(&name))
+}
+
+fn dirname(name: &str) -> &str {
+    match name.rsplit_once('/') {
+        Some(("", _)) => "/",
+        Some((dirname, _)) => dirname,
+        None => ".",
+    }
+}
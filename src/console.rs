@@ -0,0 +1,643 @@
+/// The severity of a console message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// Receives the messages scripts write to `console`.
+pub trait ConsoleSink {
+    fn write(&self, level: ConsoleLevel, message: &str);
+}
+
+/// Forwards console messages to `tracing`, at the matching level. This is the
+/// default sink.
+#[derive(Default, Clone, Copy)]
+pub struct TracingConsoleSink;
+
+impl ConsoleSink for TracingConsoleSink {
+    fn write(&self, level: ConsoleLevel, message: &str) {
+        match level {
+            ConsoleLevel::Trace => tracing::trace!(target: "unijs::console", "{}", message),
+            ConsoleLevel::Debug => tracing::debug!(target: "unijs::console", "{}", message),
+            ConsoleLevel::Info => tracing::info!(target: "unijs::console", "{}", message),
+            ConsoleLevel::Warn => tracing::warn!(target: "unijs::console", "{}", message),
+            ConsoleLevel::Error => tracing::error!(target: "unijs::console", "{}", message),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{collections::HashMap, rc::Rc, time::Instant};
+
+    use super::{ConsoleLevel, ConsoleSink, TracingConsoleSink};
+
+    /// Nested objects deeper than this are abbreviated, like `util.inspect`.
+    const MAX_DEPTH: usize = 2;
+    const MAX_ITEMS: usize = 100;
+
+    /// Where console messages go, and the state of `console.time`. Stored in an
+    /// isolate slot.
+    pub(crate) struct Console {
+        pub(crate) sink: Rc<dyn ConsoleSink>,
+        timers: HashMap<String, Instant>,
+    }
+
+    impl Default for Console {
+        fn default() -> Self {
+            Self {
+                sink: Rc::new(TracingConsoleSink),
+                timers: HashMap::new(),
+            }
+        }
+    }
+
+    /// Installs `console` on the global object.
+    pub(crate) fn install(scope: &mut v8::HandleScope) {
+        fn define(
+            scope: &mut v8::HandleScope,
+            console: v8::Local<v8::Object>,
+            name: &str,
+            callback: impl v8::MapFnTo<v8::FunctionCallback>,
+        ) {
+            let name = v8::String::new(scope, name).unwrap();
+            let function = v8::Function::new(scope, callback).unwrap();
+            console.set(scope, name.into(), function.into());
+        }
+
+        let console = v8::Object::new(scope);
+        define(scope, console, "log", log);
+        define(scope, console, "info", info);
+        define(scope, console, "debug", debug);
+        define(scope, console, "warn", warn);
+        define(scope, console, "error", error);
+        define(scope, console, "trace", trace);
+        define(scope, console, "assert", assert);
+        define(scope, console, "table", table);
+        define(scope, console, "time", time);
+        define(scope, console, "timeLog", time_log);
+        define(scope, console, "timeEnd", time_end);
+        let global = scope.get_current_context().global(scope);
+        let name = v8::String::new(scope, "console").unwrap();
+        global.set(scope, name.into(), console.into());
+    }
+
+    fn write(scope: &mut v8::HandleScope, level: ConsoleLevel, message: &str) {
+        if let Some(console) = scope.get_slot::<Console>() {
+            let sink = console.sink.clone();
+            sink.write(level, message);
+        }
+    }
+
+    /// Defines callbacks that format their arguments like `console.log` and
+    /// write them at a fixed level.
+    macro_rules! log_callbacks {
+        ($($name:ident => $level:ident),* $(,)?) => {
+            $(
+                fn $name(
+                    scope: &mut v8::HandleScope,
+                    args: v8::FunctionCallbackArguments,
+                    _: v8::ReturnValue,
+                ) {
+                    let message = format_message(scope, &args, 0);
+                    write(scope, ConsoleLevel::$level, &message);
+                }
+            )*
+        };
+    }
+
+    log_callbacks! {
+        log => Info,
+        info => Info,
+        debug => Debug,
+        warn => Warn,
+        error => Error,
+    }
+
+    fn trace(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        _: v8::ReturnValue,
+    ) {
+        let mut message = format!("Trace: {}", format_message(scope, &args, 0));
+        if let Some(stack) = v8::StackTrace::current_stack_trace(scope, 10) {
+            for i in 0..stack.get_frame_count() {
+                let Some(frame) = stack.get_frame(scope, i) else {
+                    continue;
+                };
+                let script = frame
+                    .get_script_name(scope)
+                    .map(|name| name.to_rust_string_lossy(scope))
+                    .unwrap_or_else(|| "<anonymous>".to_owned());
+                let location = format!(
+                    "{}:{}:{}",
+                    script,
+                    frame.get_line_number(),
+                    frame.get_column()
+                );
+                match frame.get_function_name(scope) {
+                    Some(name) if name.length() > 0 => {
+                        let name = name.to_rust_string_lossy(scope);
+                        message.push_str(&format!("\n    at {} ({})", name, location));
+                    }
+                    _ => message.push_str(&format!("\n    at {}", location)),
+                }
+            }
+        }
+        let message = crate::source_map::remap_stack(scope, message);
+        write(scope, ConsoleLevel::Trace, &message);
+    }
+
+    fn assert(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        _: v8::ReturnValue,
+    ) {
+        if args.get(0).boolean_value(scope) {
+            return;
+        }
+        let message = if args.length() > 1 {
+            format!("Assertion failed: {}", format_message(scope, &args, 1))
+        } else {
+            "Assertion failed".to_owned()
+        };
+        write(scope, ConsoleLevel::Error, &message);
+    }
+
+    fn table(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        _: v8::ReturnValue,
+    ) {
+        let data = v8::Local::new(scope, args.get(0));
+        let message = match v8::Local::<v8::Object>::try_from(data) {
+            Ok(rows) if !data.is_function() => format_table(scope, rows),
+            _ => None,
+        };
+        let message = message.unwrap_or_else(|| format_message(scope, &args, 0));
+        write(scope, ConsoleLevel::Info, &message);
+    }
+
+    fn time(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        _: v8::ReturnValue,
+    ) {
+        let label = label(scope, &args);
+        let console = scope.get_slot_mut::<Console>().unwrap();
+        if console.timers.contains_key(&label) {
+            let message = format!("Timer '{}' already exists", label);
+            write(scope, ConsoleLevel::Warn, &message);
+        } else {
+            console.timers.insert(label, Instant::now());
+        }
+    }
+
+    fn time_log(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        _: v8::ReturnValue,
+    ) {
+        let label = label(scope, &args);
+        let start = scope.get_slot::<Console>().unwrap().timers.get(&label).copied();
+        match start {
+            Some(start) => {
+                let mut message = format!("{}: {}", label, format_elapsed(&start));
+                if args.length() > 1 {
+                    message.push(' ');
+                    message.push_str(&format_message(scope, &args, 1));
+                }
+                write(scope, ConsoleLevel::Info, &message);
+            }
+            None => {
+                let message = format!("Timer '{}' does not exist", label);
+                write(scope, ConsoleLevel::Warn, &message);
+            }
+        }
+    }
+
+    fn time_end(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        _: v8::ReturnValue,
+    ) {
+        let label = label(scope, &args);
+        let console = scope.get_slot_mut::<Console>().unwrap();
+        match console.timers.remove(&label) {
+            Some(start) => {
+                let message = format!("{}: {}", label, format_elapsed(&start));
+                write(scope, ConsoleLevel::Info, &message);
+            }
+            None => {
+                let message = format!("Timer '{}' does not exist", label);
+                write(scope, ConsoleLevel::Warn, &message);
+            }
+        }
+    }
+
+    fn label(scope: &mut v8::HandleScope, args: &v8::FunctionCallbackArguments) -> String {
+        let label = args.get(0);
+        if label.is_undefined() {
+            "default".to_owned()
+        } else {
+            label.to_rust_string_lossy(scope)
+        }
+    }
+
+    fn format_elapsed(start: &Instant) -> String {
+        let millis = start.elapsed().as_secs_f64() * 1000.0;
+        if millis < 1000.0 {
+            format!("{:.3}ms", millis)
+        } else {
+            format!("{:.3}s", millis / 1000.0)
+        }
+    }
+
+    /// Formats arguments like `console.log`: a leading string may contain `%s`,
+    /// `%d`, `%i`, `%f`, `%o`, `%O`, `%j` and `%%`, and every other argument is
+    /// inspected and separated by a space.
+    fn format_message(
+        scope: &mut v8::HandleScope,
+        args: &v8::FunctionCallbackArguments,
+        start: i32,
+    ) -> String {
+        let mut parts = vec![];
+        // arguments are only valid for the callback's own scope
+        let args = (0..args.length())
+            .map(|i| v8::Local::new(scope, args.get(i)))
+            .collect::<Vec<_>>();
+        let mut next = start as usize;
+        if next < args.len() && args[next].is_string() {
+            let template = args[next].to_rust_string_lossy(scope);
+            next += 1;
+            let mut message = String::new();
+            let mut chars = template.chars().peekable();
+            while let Some(c) = chars.next() {
+                let Some(&specifier) = chars.peek().filter(|_| c == '%') else {
+                    message.push(c);
+                    continue;
+                };
+                if specifier == '%' {
+                    chars.next();
+                    message.push('%');
+                    continue;
+                }
+                if !"sdifoOj".contains(specifier) || next >= args.len() {
+                    message.push(c);
+                    continue;
+                }
+                chars.next();
+                let arg = args[next];
+                next += 1;
+                match specifier {
+                    's' if arg.is_string() => message.push_str(&arg.to_rust_string_lossy(scope)),
+                    's' | 'o' | 'O' => message.push_str(&inspect(scope, arg)),
+                    'd' | 'i' | 'f' => {
+                        let number = arg.number_value(scope).unwrap_or(f64::NAN);
+                        let number = if specifier == 'f' { number } else { number.trunc() };
+                        let number = v8::Number::new(scope, number);
+                        message.push_str(&number.to_rust_string_lossy(scope));
+                    }
+                    _ => match v8::json::stringify(scope, arg) {
+                        Some(json) => message.push_str(&json.to_rust_string_lossy(scope)),
+                        None => message.push_str("undefined"),
+                    },
+                }
+            }
+            parts.push(message);
+        }
+        for &arg in &args[next..] {
+            if arg.is_string() {
+                parts.push(arg.to_rust_string_lossy(scope));
+            } else {
+                parts.push(inspect(scope, arg));
+            }
+        }
+        parts.join(" ")
+    }
+
+    /// Formats a value like `util.inspect`.
+    fn inspect<'s>(scope: &mut v8::HandleScope<'s>, value: v8::Local<'s, v8::Value>) -> String {
+        let mut seen = vec![];
+        inspect_value(scope, value, 0, &mut seen)
+    }
+
+    fn inspect_value<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        value: v8::Local<'s, v8::Value>,
+        depth: usize,
+        seen: &mut Vec<v8::Local<'s, v8::Object>>,
+    ) -> String {
+        if value.is_string() {
+            return quote(&value.to_rust_string_lossy(scope));
+        }
+        if value.is_number() {
+            let number = value.number_value(scope).unwrap_or(f64::NAN);
+            if number == 0.0 && number.is_sign_negative() {
+                return "-0".to_owned();
+            }
+            return value.to_rust_string_lossy(scope);
+        }
+        if value.is_big_int() {
+            return format!("{}n", value.to_rust_string_lossy(scope));
+        }
+        if let Ok(symbol) = v8::Local::<v8::Symbol>::try_from(value) {
+            let description = symbol.description(scope);
+            return if description.is_undefined() {
+                "Symbol()".to_owned()
+            } else {
+                format!("Symbol({})", description.to_rust_string_lossy(scope))
+            };
+        }
+        let Ok(object) = v8::Local::<v8::Object>::try_from(value) else {
+            return value.to_rust_string_lossy(scope);
+        };
+
+        if let Ok(function) = v8::Local::<v8::Function>::try_from(value) {
+            let name = function.get_name(scope).to_rust_string_lossy(scope);
+            return if name.is_empty() {
+                "[Function (anonymous)]".to_owned()
+            } else {
+                format!("[Function: {}]", name)
+            };
+        }
+        if value.is_native_error() {
+            let key = v8::String::new(scope, "stack").unwrap();
+            if let Some(stack) = object.get(scope, key.into()).filter(|stack| stack.is_string()) {
+                let stack = stack.to_rust_string_lossy(scope);
+                return crate::source_map::remap_stack(scope, stack);
+            }
+            return value.to_rust_string_lossy(scope);
+        }
+        if value.is_date() || value.is_reg_exp() {
+            return value.to_rust_string_lossy(scope);
+        }
+        if let Ok(promise) = v8::Local::<v8::Promise>::try_from(value) {
+            return match promise.state() {
+                v8::PromiseState::Pending => "Promise { <pending> }".to_owned(),
+                v8::PromiseState::Fulfilled => {
+                    let result = promise.result(scope);
+                    format!("Promise {{ {} }}", inspect_value(scope, result, depth + 1, seen))
+                }
+                v8::PromiseState::Rejected => {
+                    let result = promise.result(scope);
+                    format!(
+                        "Promise {{ <rejected> {} }}",
+                        inspect_value(scope, result, depth + 1, seen)
+                    )
+                }
+            };
+        }
+
+        if seen.iter().any(|seen| seen.strict_equals(object.into())) {
+            return "[Circular]".to_owned();
+        }
+        let constructor = object.get_constructor_name().to_rust_string_lossy(scope);
+        if depth > MAX_DEPTH {
+            return if value.is_array() {
+                "[Array]".to_owned()
+            } else {
+                format!("[{}]", constructor)
+            };
+        }
+
+        seen.push(object);
+        let (prefix, items) = if let Ok(array) = v8::Local::<v8::Array>::try_from(value) {
+            let mut items = vec![];
+            for i in 0..array.length().min(MAX_ITEMS as u32) {
+                let item = array
+                    .get_index(scope, i)
+                    .unwrap_or_else(|| v8::undefined(scope).into());
+                items.push(inspect_value(scope, item, depth + 1, seen));
+            }
+            if array.length() as usize > MAX_ITEMS {
+                items.push(format!("... {} more items", array.length() as usize - MAX_ITEMS));
+            }
+            (String::new(), Some(items))
+        } else if let Ok(map) = v8::Local::<v8::Map>::try_from(value) {
+            let entries = map.as_array(scope);
+            let mut items = vec![];
+            for i in (0..entries.length()).step_by(2) {
+                let key = entries.get_index(scope, i).unwrap();
+                let value = entries.get_index(scope, i + 1).unwrap();
+                let key = inspect_value(scope, key, depth + 1, seen);
+                let value = inspect_value(scope, value, depth + 1, seen);
+                items.push(format!("{} => {}", key, value));
+            }
+            (format!("Map({}) ", map.size()), Some(items))
+        } else if let Ok(set) = v8::Local::<v8::Set>::try_from(value) {
+            let entries = set.as_array(scope);
+            let mut items = vec![];
+            for i in 0..entries.length() {
+                let item = entries.get_index(scope, i).unwrap();
+                items.push(inspect_value(scope, item, depth + 1, seen));
+            }
+            (format!("Set({}) ", set.size()), Some(items))
+        } else {
+            (String::new(), None)
+        };
+        let result = match items {
+            Some(items) if prefix.is_empty() => {
+                if items.is_empty() {
+                    "[]".to_owned()
+                } else {
+                    format!("[ {} ]", items.join(", "))
+                }
+            }
+            Some(items) => {
+                if items.is_empty() {
+                    format!("{}{{}}", prefix)
+                } else {
+                    format!("{}{{ {} }}", prefix, items.join(", "))
+                }
+            }
+            None => {
+                let mut properties = vec![];
+                if let Some(names) =
+                    object.get_own_property_names(scope, v8::GetPropertyNamesArgs::default())
+                {
+                    for i in 0..names.length() {
+                        let Some(name) = names.get_index(scope, i) else {
+                            continue;
+                        };
+                        let Some(value) = inspect_property(scope, object, name, depth, seen)
+                        else {
+                            continue;
+                        };
+                        let name = name.to_rust_string_lossy(scope);
+                        properties.push(format!("{}: {}", property_key(&name), value));
+                    }
+                }
+                let prefix = if constructor == "Object" {
+                    String::new()
+                } else {
+                    format!("{} ", constructor)
+                };
+                if properties.is_empty() {
+                    format!("{}{{}}", prefix)
+                } else {
+                    format!("{}{{ {} }}", prefix, properties.join(", "))
+                }
+            }
+        };
+        seen.pop();
+        result
+    }
+
+    /// Inspects an own property without running its getter, which is shown as
+    /// `[Getter]`, `[Setter]` or `[Getter/Setter]` like `util.inspect`.
+    fn inspect_property<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        object: v8::Local<'s, v8::Object>,
+        name: v8::Local<'s, v8::Value>,
+        depth: usize,
+        seen: &mut Vec<v8::Local<'s, v8::Object>>,
+    ) -> Option<String> {
+        let name = v8::Local::<v8::Name>::try_from(name).ok()?;
+        let descriptor = object.get_own_property_descriptor(scope, name)?;
+        let descriptor = v8::Local::<v8::Object>::try_from(descriptor).ok()?;
+        let field = |scope: &mut v8::HandleScope<'s>, key: &str| {
+            let key = v8::String::new(scope, key).unwrap();
+            descriptor
+                .get(scope, key.into())
+                .filter(|value| !value.is_undefined())
+        };
+        let getter = field(scope, "get");
+        let setter = field(scope, "set");
+        match (getter, setter) {
+            (Some(_), Some(_)) => Some("[Getter/Setter]".to_owned()),
+            (Some(_), None) => Some("[Getter]".to_owned()),
+            (None, Some(_)) => Some("[Setter]".to_owned()),
+            (None, None) => {
+                let value = field(scope, "value").unwrap_or_else(|| v8::undefined(scope).into());
+                Some(inspect_value(scope, value, depth + 1, seen))
+            }
+        }
+    }
+
+    fn quote(string: &str) -> String {
+        let mut quoted = String::from("'");
+        for c in string.chars() {
+            match c {
+                '\'' => quoted.push_str("\\'"),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('\'');
+        quoted
+    }
+
+    /// Property names are only quoted when they aren't identifiers.
+    fn property_key(name: &str) -> String {
+        let mut chars = name.chars();
+        let identifier = chars
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+            && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+        if identifier {
+            name.to_owned()
+        } else {
+            quote(name)
+        }
+    }
+
+    /// Formats an array or object of rows as a table, like `console.table`.
+    /// Returns `None` if there is nothing to tabulate.
+    fn format_table<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        rows: v8::Local<'s, v8::Object>,
+    ) -> Option<String> {
+        let row_names = rows.get_own_property_names(scope, v8::GetPropertyNamesArgs::default())?;
+        let mut index = vec![];
+        let mut columns: Vec<String> = vec![];
+        let mut cells: Vec<HashMap<String, String>> = vec![];
+        let mut values: Vec<Option<String>> = vec![];
+        for i in 0..row_names.length() {
+            let name = row_names.get_index(scope, i)?;
+            let row = rows.get(scope, name)?;
+            index.push(name.to_rust_string_lossy(scope));
+            let mut row_cells = HashMap::new();
+            match v8::Local::<v8::Object>::try_from(row) {
+                Ok(row) if !row.is_function() => {
+                    let names =
+                        row.get_own_property_names(scope, v8::GetPropertyNamesArgs::default())?;
+                    for j in 0..names.length() {
+                        let name = names.get_index(scope, j)?;
+                        let value = row.get(scope, name)?;
+                        let name = name.to_rust_string_lossy(scope);
+                        if !columns.contains(&name) {
+                            columns.push(name.clone());
+                        }
+                        let mut seen = vec![];
+                        row_cells.insert(name, inspect_value(scope, value, 1, &mut seen));
+                    }
+                    values.push(None);
+                }
+                _ => values.push(Some(inspect(scope, row))),
+            }
+            cells.push(row_cells);
+        }
+
+        let mut header = vec!["(index)".to_owned()];
+        header.extend(columns.iter().cloned());
+        let has_values = values.iter().any(|value| value.is_some());
+        if has_values {
+            header.push("Values".to_owned());
+        }
+        let body = index
+            .into_iter()
+            .zip(cells)
+            .zip(values)
+            .map(|((index, mut cells), value)| {
+                let mut row = vec![index];
+                row.extend(
+                    columns
+                        .iter()
+                        .map(|column| cells.remove(column).unwrap_or_default()),
+                );
+                if has_values {
+                    row.push(value.unwrap_or_default());
+                }
+                row
+            })
+            .collect::<Vec<_>>();
+
+        let widths = (0..header.len())
+            .map(|i| {
+                std::iter::once(&header)
+                    .chain(&body)
+                    .map(|row| row[i].chars().count() + 2)
+                    .max()
+                    .unwrap_or(2)
+            })
+            .collect::<Vec<_>>();
+        let line = |left: &str, middle: &str, right: &str| {
+            let cells = widths.iter().map(|width| "─".repeat(*width)).collect::<Vec<_>>();
+            format!("{}{}{}", left, cells.join(middle), right)
+        };
+        let row = |row: &[String]| {
+            let cells = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| {
+                    let padding = width - cell.chars().count();
+                    let left = padding / 2;
+                    format!("{}{}{}", " ".repeat(left), cell, " ".repeat(padding - left))
+                })
+                .collect::<Vec<_>>();
+            format!("│{}│", cells.join("│"))
+        };
+        let mut table = vec![line("┌", "┬", "┐"), row(&header), line("├", "┼", "┤")];
+        table.extend(body.iter().map(|cells| row(cells)));
+        table.push(line("└", "┴", "┘"));
+        Some(table.join("\n"))
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
@@ -0,0 +1,202 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{JsError, Scope, Value};
+
+/// A JavaScript `Date`, a point in time stored as milliseconds since the Unix
+/// epoch. Invalid dates, like `new Date("nope")`, have a `NaN` timestamp.
+#[derive(Clone)]
+pub struct Date {
+    #[cfg(not(target_arch = "wasm32"))]
+    date: v8::Global<v8::Date>,
+    #[cfg(target_arch = "wasm32")]
+    date: js_sys::Date,
+}
+
+impl Date {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        date: v8::Local<'b, v8::Date>,
+    ) -> Self {
+        Self {
+            date: v8::Global::new(scope, date),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(&self, scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::Date> {
+        v8::Local::new(scope, &self.date)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(date: js_sys::Date) -> Self {
+        Self { date }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> js_sys::Date {
+        self.date.clone()
+    }
+
+    /// Creates a date from milliseconds since the Unix epoch, like
+    /// `new Date(timestamp)`.
+    #[allow(unused_variables)]
+    pub fn new(scope: &mut Scope, timestamp: f64) -> Result<Self, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.try_enter(|scope| {
+                let date = v8::Date::new(scope, timestamp)?;
+                Some(Self::from_v8(scope, date))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(Self {
+                date: js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(timestamp)),
+            })
+        }
+    }
+
+    /// Creates a date from a [`SystemTime`], rounded down to milliseconds.
+    pub fn from_system_time(scope: &mut Scope, time: SystemTime) -> Result<Self, JsError> {
+        let timestamp = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_millis() as f64,
+            // round times before the epoch down, like the positive case
+            Err(error) => {
+                let duration = error.duration();
+                let partial = duration.subsec_nanos() % 1_000_000 != 0;
+                -((duration.as_millis() + partial as u128) as f64)
+            }
+        };
+        Self::new(scope, timestamp)
+    }
+
+    /// Milliseconds since the Unix epoch, or `NaN` for an invalid date.
+    #[allow(unused_variables)]
+    pub fn timestamp(&self, scope: &mut Scope) -> f64 {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let date = self.date.clone();
+            scope.enter(move |scope| v8::Local::new(scope, date).value_of())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.date.get_time()
+        }
+    }
+
+    pub fn is_valid(&self, scope: &mut Scope) -> bool {
+        !self.timestamp(scope).is_nan()
+    }
+
+    /// The date as a [`SystemTime`], or `None` for an invalid date.
+    pub fn to_system_time(&self, scope: &mut Scope) -> Option<SystemTime> {
+        let timestamp = self.timestamp(scope);
+        if timestamp.is_nan() {
+            return None;
+        }
+        let millis = timestamp as i64;
+        if millis >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_millis(millis as u64))
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_millis(millis.unsigned_abs()))
+        }
+    }
+
+    /// Formats the date like `Date.prototype.toISOString`, which is also what
+    /// `JSON.stringify` writes. Returns `None` for an invalid date.
+    pub fn to_iso_string(&self, scope: &mut Scope) -> Option<String> {
+        iso_string(self.timestamp(scope))
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn from_chrono<Tz: chrono::TimeZone>(
+        scope: &mut Scope,
+        time: &chrono::DateTime<Tz>,
+    ) -> Result<Self, JsError> {
+        Self::new(scope, time.timestamp_millis() as f64)
+    }
+
+    /// The date in UTC, or `None` for an invalid date.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(&self, scope: &mut Scope) -> Option<chrono::DateTime<chrono::Utc>> {
+        let timestamp = self.timestamp(scope);
+        if timestamp.is_nan() {
+            return None;
+        }
+        chrono::DateTime::from_timestamp_millis(timestamp as i64)
+    }
+
+    #[cfg(feature = "time")]
+    pub fn from_offset_date_time(
+        scope: &mut Scope,
+        time: time::OffsetDateTime,
+    ) -> Result<Self, JsError> {
+        Self::new(scope, time.unix_timestamp_nanos().div_euclid(1_000_000) as f64)
+    }
+
+    /// The date in UTC, or `None` for an invalid date.
+    #[cfg(feature = "time")]
+    pub fn to_offset_date_time(&self, scope: &mut Scope) -> Option<time::OffsetDateTime> {
+        let timestamp = self.timestamp(scope);
+        if timestamp.is_nan() {
+            return None;
+        }
+        time::OffsetDateTime::from_unix_timestamp_nanos(timestamp as i128 * 1_000_000).ok()
+    }
+}
+
+/// Formats a timestamp like `Date.prototype.toISOString`.
+pub(crate) fn iso_string(timestamp: f64) -> Option<String> {
+    if timestamp.is_nan() {
+        return None;
+    }
+    let millis = timestamp as i64;
+    let days = millis.div_euclid(86_400_000);
+    let time = millis.rem_euclid(86_400_000);
+    let (year, month, day) = civil_from_days(days);
+    let year = if (0..=9999).contains(&year) {
+        format!("{:04}", year)
+    } else {
+        format!("{:+07}", year)
+    };
+    Some(format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        time / 3_600_000,
+        time / 60_000 % 60,
+        time / 1000 % 60,
+        time % 1000,
+    ))
+}
+
+/// Converts days since the Unix epoch to a proleptic Gregorian
+/// `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+impl std::fmt::Debug for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[date]")
+    }
+}
+
+impl From<Date> for Value {
+    fn from(value: Date) -> Self {
+        Value::Date(value)
+    }
+}
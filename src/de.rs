@@ -0,0 +1,624 @@
+use serde::{
+    de::{self, value::StringDeserializer, DeserializeSeed, Error as _, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, Deserialize, Deserializer as _,
+};
+
+use crate::{
+    ser::{RawScope, RawValue, VALUE_SLOT, VALUE_TOKEN},
+    BigInt, Function, SerdeError, Value,
+};
+
+/// What a [`RawValue`] looks like to serde. Containers hold their items, which
+/// are deserialized lazily.
+enum Kind<'s> {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    BigInt(BigInt),
+    String(String),
+    Seq(Vec<RawValue<'s>>),
+    Bytes(Vec<u8>),
+    Map(Vec<(RawValue<'s>, RawValue<'s>)>),
+    Object(Vec<(String, RawValue<'s>)>),
+    /// Functions and symbols, which JSON leaves out.
+    Opaque,
+}
+
+/// Walks engine values directly into Rust data.
+pub(crate) struct Deserializer<'a, 's> {
+    scope: &'a mut RawScope<'s>,
+    value: RawValue<'s>,
+}
+
+impl<'a, 's> Deserializer<'a, 's> {
+    pub(crate) fn new(scope: &'a mut RawScope<'s>, value: RawValue<'s>) -> Self {
+        Self { scope, value }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn kind(&mut self) -> Result<Kind<'s>, SerdeError> {
+        let scope = &mut *self.scope;
+        let value = self.value;
+        let exception = || SerdeError::custom("failed to read a value");
+        Ok(if value.is_undefined() {
+            Kind::Undefined
+        } else if value.is_null() {
+            Kind::Null
+        } else if value.is_boolean() {
+            Kind::Bool(value.boolean_value(scope))
+        } else if value.is_number() {
+            Kind::Number(value.number_value(scope).ok_or_else(exception)?)
+        } else if value.is_big_int() {
+            Kind::BigInt(BigInt::from_v8(value.try_into().unwrap()))
+        } else if value.is_string() {
+            Kind::String(value.to_rust_string_lossy(scope))
+        } else if value.is_function() || value.is_symbol() {
+            Kind::Opaque
+        } else if value.is_array() {
+            let array: v8::Local<v8::Array> = value.try_into().unwrap();
+            let mut items = Vec::with_capacity(array.length() as usize);
+            for i in 0..array.length() {
+                items.push(array.get_index(scope, i).ok_or_else(exception)?);
+            }
+            Kind::Seq(items)
+        } else if value.is_array_buffer() {
+            let buffer: v8::Local<v8::ArrayBuffer> = value.try_into().unwrap();
+            let store = buffer.get_backing_store();
+            Kind::Bytes(store.iter().map(|byte| byte.get()).collect())
+        } else if value.is_uint8_array() || value.is_uint8_clamped_array() {
+            let view: v8::Local<v8::ArrayBufferView> = value.try_into().unwrap();
+            let mut bytes = vec![0; view.byte_length()];
+            view.copy_contents(&mut bytes);
+            Kind::Bytes(bytes)
+        } else if value.is_typed_array() {
+            let array: v8::Local<v8::TypedArray> = value.try_into().unwrap();
+            let mut items = Vec::with_capacity(array.length());
+            for i in 0..array.length() as u32 {
+                items.push(array.get_index(scope, i).ok_or_else(exception)?);
+            }
+            Kind::Seq(items)
+        } else if value.is_date() {
+            let date: v8::Local<v8::Date> = value.try_into().unwrap();
+            crate::date::iso_string(date.value_of()).map_or(Kind::Null, Kind::String)
+        } else if value.is_map() {
+            let map: v8::Local<v8::Map> = value.try_into().unwrap();
+            // keys and values are interleaved
+            let array = map.as_array(scope);
+            let mut entries = Vec::with_capacity(map.size());
+            for i in (0..array.length()).step_by(2) {
+                let key = array.get_index(scope, i).ok_or_else(exception)?;
+                let value = array.get_index(scope, i + 1).ok_or_else(exception)?;
+                entries.push((key, value));
+            }
+            Kind::Map(entries)
+        } else if value.is_set() {
+            let set: v8::Local<v8::Set> = value.try_into().unwrap();
+            let array = set.as_array(scope);
+            let mut items = Vec::with_capacity(set.size());
+            for i in 0..array.length() {
+                items.push(array.get_index(scope, i).ok_or_else(exception)?);
+            }
+            Kind::Seq(items)
+        } else if value.is_object() {
+            let object: v8::Local<v8::Object> = value.try_into().unwrap();
+            let names = object
+                .get_own_property_names(
+                    scope,
+                    v8::GetPropertyNamesArgs {
+                        key_conversion: v8::KeyConversionMode::ConvertToString,
+                        ..Default::default()
+                    },
+                )
+                .ok_or_else(exception)?;
+            let mut entries = Vec::with_capacity(names.length() as usize);
+            for i in 0..names.length() {
+                let name = names.get_index(scope, i).ok_or_else(exception)?;
+                let value = object.get(scope, name).ok_or_else(exception)?;
+                // undefined properties are left out, like in JSON
+                if !value.is_undefined() {
+                    entries.push((name.to_rust_string_lossy(scope), value));
+                }
+            }
+            Kind::Object(entries)
+        } else {
+            Kind::Opaque
+        })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn kind(&mut self) -> Result<Kind<'s>, SerdeError> {
+        use wasm_bindgen::JsCast;
+        let value = &self.value;
+        Ok(if value.is_undefined() {
+            Kind::Undefined
+        } else if value.is_null() {
+            Kind::Null
+        } else if let Some(value) = value.as_bool() {
+            Kind::Bool(value)
+        } else if let Some(value) = value.as_f64() {
+            Kind::Number(value)
+        } else if value.is_bigint() {
+            Kind::BigInt(BigInt::from_web(value.clone().unchecked_into()))
+        } else if let Some(value) = value.as_string() {
+            Kind::String(value)
+        } else if value.is_function() || value.is_symbol() {
+            Kind::Opaque
+        } else if js_sys::Array::is_array(value) {
+            Kind::Seq(js_sys::Array::from(value).iter().collect())
+        } else if value.is_instance_of::<js_sys::ArrayBuffer>()
+            || value.is_instance_of::<js_sys::Uint8Array>()
+            || value.is_instance_of::<js_sys::Uint8ClampedArray>()
+        {
+            Kind::Bytes(js_sys::Uint8Array::new(value).to_vec())
+        } else if js_sys::ArrayBuffer::is_view(value) {
+            Kind::Seq(js_sys::Array::from(value).iter().collect())
+        } else if let Some(date) = value.dyn_ref::<js_sys::Date>() {
+            crate::date::iso_string(date.get_time()).map_or(Kind::Null, Kind::String)
+        } else if value.is_instance_of::<js_sys::Map>() {
+            Kind::Map(
+                js_sys::Array::from(value)
+                    .iter()
+                    .map(|entry| {
+                        let entry = js_sys::Array::from(&entry);
+                        (entry.get(0), entry.get(1))
+                    })
+                    .collect(),
+            )
+        } else if value.is_instance_of::<js_sys::Set>() {
+            Kind::Seq(js_sys::Array::from(value).iter().collect())
+        } else if let Some(object) = value.dyn_ref::<js_sys::Object>() {
+            let mut entries = vec![];
+            for name in js_sys::Object::keys(object).iter() {
+                let value = js_sys::Reflect::get(object, &name)
+                    .map_err(|error| SerdeError::Exception(crate::JsError::from_web(error)))?;
+                // undefined properties are left out, like in JSON
+                if !value.is_undefined() {
+                    entries.push((name.as_string().unwrap_or_default(), value));
+                }
+            }
+            Kind::Object(entries)
+        } else {
+            Kind::Opaque
+        })
+    }
+
+    #[allow(unused_variables)]
+    fn to_value(&mut self) -> Value {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Value::from_v8(self.scope, self.value)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Value::from_web(self.value.clone())
+        }
+    }
+
+    fn is_nullish(&self) -> bool {
+        self.value.is_undefined() || self.value.is_null()
+    }
+
+    fn visit<'de, V: Visitor<'de>>(
+        self,
+        kind: Kind<'s>,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match kind {
+            Kind::Undefined | Kind::Null | Kind::Opaque => visitor.visit_unit(),
+            Kind::Bool(value) => visitor.visit_bool(value),
+            // integers are visited as such, so they can become any integer type. The
+            // cast saturates, so 2^64 and above would otherwise become u64::MAX
+            Kind::Number(value)
+                if value < 18446744073709551616.0 && value as u64 as f64 == value =>
+            {
+                visitor.visit_u64(value as u64)
+            }
+            Kind::Number(value) if value as i64 as f64 == value => visitor.visit_i64(value as i64),
+            Kind::Number(value) => visitor.visit_f64(value),
+            Kind::BigInt(value) => {
+                if let Some(value) = value.to_u64() {
+                    visitor.visit_u64(value)
+                } else if let Some(value) = value.to_i64() {
+                    visitor.visit_i64(value)
+                } else if let Some(value) = value.to_u128() {
+                    visitor.visit_u128(value)
+                } else if let Some(value) = value.to_i128() {
+                    visitor.visit_i128(value)
+                } else {
+                    visitor.visit_string(value.to_string())
+                }
+            }
+            Kind::String(value) => visitor.visit_string(value),
+            Kind::Seq(items) => {
+                let mut seq = SeqAccess {
+                    scope: self.scope,
+                    items: items.into_iter(),
+                };
+                let value = visitor.visit_seq(&mut seq)?;
+                match seq.items.len() {
+                    0 => Ok(value),
+                    remaining => Err(SerdeError::invalid_length(
+                        remaining,
+                        &"fewer elements in array",
+                    )),
+                }
+            }
+            Kind::Bytes(bytes) => {
+                de::value::SeqDeserializer::new(bytes.into_iter()).deserialize_any(visitor)
+            }
+            Kind::Map(entries) => visitor.visit_map(MapAccess {
+                scope: self.scope,
+                entries: entries.into_iter(),
+                value: None,
+            }),
+            Kind::Object(entries) => visitor.visit_map(ObjectAccess {
+                scope: self.scope,
+                entries: entries.into_iter(),
+                value: None,
+            }),
+        }
+    }
+}
+
+impl<'de, 'a, 's> de::Deserializer<'de> for Deserializer<'a, 's> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
+        let kind = self.kind()?;
+        self.visit(kind, visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.is_nullish() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.kind()? {
+            Kind::Bytes(bytes) => visitor.visit_byte_buf(bytes),
+            kind => self.visit(kind, visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        mut self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if name == VALUE_TOKEN {
+            let value = self.to_value();
+            VALUE_SLOT.with(|slot| *slot.borrow_mut() = Some(value));
+            visitor.visit_unit()
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    /// Enums are either a variant name, or an object with a single property
+    /// named after the variant.
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.kind()? {
+            Kind::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Kind::Object(mut entries) if entries.len() == 1 => {
+                let (variant, value) = entries.pop().unwrap();
+                visitor.visit_enum(EnumAccess {
+                    scope: self.scope,
+                    variant,
+                    value,
+                })
+            }
+            _ => Err(SerdeError::custom(
+                "expected a string or an object with a single property",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de, 'a, 's> de::VariantAccess<'de> for Deserializer<'a, 's> {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+struct SeqAccess<'a, 's> {
+    scope: &'a mut RawScope<'s>,
+    items: std::vec::IntoIter<RawValue<'s>>,
+}
+
+impl<'de, 'a, 's> de::SeqAccess<'de> for SeqAccess<'a, 's> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.items.next() {
+            Some(value) => seed.deserialize(Deserializer::new(self.scope, value)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+/// The entries of a `Map`, whose keys are any value.
+struct MapAccess<'a, 's> {
+    scope: &'a mut RawScope<'s>,
+    entries: std::vec::IntoIter<(RawValue<'s>, RawValue<'s>)>,
+    value: Option<RawValue<'s>>,
+}
+
+impl<'de, 'a, 's> de::MapAccess<'de> for MapAccess<'a, 's> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(Deserializer::new(self.scope, key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| SerdeError::custom("map value without a key"))?;
+        seed.deserialize(Deserializer::new(self.scope, value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// The properties of an object, whose keys are strings.
+struct ObjectAccess<'a, 's> {
+    scope: &'a mut RawScope<'s>,
+    entries: std::vec::IntoIter<(String, RawValue<'s>)>,
+    value: Option<RawValue<'s>>,
+}
+
+impl<'de, 'a, 's> de::MapAccess<'de> for ObjectAccess<'a, 's> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(KeyDeserializer(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| SerdeError::custom("map value without a key"))?;
+        seed.deserialize(Deserializer::new(self.scope, value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// A property name, which can also become a number or a bool, since objects
+/// stringify their keys.
+struct KeyDeserializer(String);
+
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => visitor.visit_string(self.0),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0)
+    }
+
+    deserialize_parsed_key! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// An enum written as `{ variant: value }`.
+struct EnumAccess<'a, 's> {
+    scope: &'a mut RawScope<'s>,
+    variant: String,
+    value: RawValue<'s>,
+}
+
+impl<'de, 'a, 's> de::EnumAccess<'de> for EnumAccess<'a, 's> {
+    type Error = SerdeError;
+    type Variant = Deserializer<'a, 's>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant: StringDeserializer<SerdeError> = self.variant.into_deserializer();
+        let variant = seed.deserialize(variant)?;
+        Ok((variant, Deserializer::new(self.scope, self.value)))
+    }
+}
+
+/// Passes the value through as is when deserialized by unijs, for example by
+/// [`Value::deserialize`]. Other deserializers can only produce primitives.
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_newtype_struct(VALUE_TOKEN, ValueVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Function {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <Value as Deserialize>::deserialize(deserializer)?
+            .into_function()
+            .ok_or_else(|| D::Error::custom("expected a function"))
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a javascript value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(VALUE_SLOT
+            .with(|slot| slot.borrow_mut().take())
+            .unwrap_or(Value::Null))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::Number(v as f64))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::Number(v as f64))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Number(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{Scope, Value};
+
+/// An error raised while running JavaScript.
+#[derive(Clone)]
+pub enum JsError {
+    /// An exception thrown by JavaScript, or raised on its behalf.
+    Exception(Exception),
+    /// Execution was interrupted by a [`TerminationHandle`](crate::TerminationHandle)
+    /// or a timeout.
+    Terminated,
+    /// Execution was interrupted because the runtime's heap limit was reached.
+    OutOfMemory,
+}
+
+/// An exception thrown while running JavaScript.
+#[derive(Clone)]
+pub struct Exception {
+    value: Option<Value>,
+    name: String,
+    message: String,
+    stack: Option<String>,
+}
+
+impl JsError {
+    pub fn new(name: &str, message: &str) -> Self {
+        Self::Exception(Exception {
+            value: None,
+            name: name.to_owned(),
+            message: message.to_owned(),
+            stack: None,
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        exception: v8::Local<'b, v8::Value>,
+    ) -> Self {
+        fn property(
+            scope: &mut v8::HandleScope<'_>,
+            object: v8::Local<'_, v8::Object>,
+            name: &str,
+        ) -> Option<String> {
+            let key = v8::String::new(scope, name)?;
+            let value = object.get(scope, key.into())?;
+            if value.is_undefined() || value.is_null() {
+                None
+            } else {
+                Some(value.to_rust_string_lossy(scope))
+            }
+        }
+
+        let value = Some(Value::from_v8(scope, exception));
+        Self::Exception(if let Ok(object) = v8::Local::<v8::Object>::try_from(exception) {
+            Exception {
+                value,
+                name: property(scope, object, "name").unwrap_or_else(|| "Error".to_owned()),
+                message: property(scope, object, "message")
+                    .unwrap_or_else(|| exception.to_rust_string_lossy(scope)),
+                stack: property(scope, object, "stack")
+                    .map(|stack| crate::source_map::remap_stack(scope, stack)),
+            }
+        } else {
+            Exception {
+                value,
+                name: "Error".to_owned(),
+                message: exception.to_rust_string_lossy(scope),
+                stack: None,
+            }
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_try_catch<'a, 'b>(
+        scope: &mut v8::TryCatch<'a, v8::HandleScope<'b>>,
+    ) -> Self {
+        if scope.has_terminated() {
+            if crate::termination::heap_limit_exceeded(scope) {
+                Self::OutOfMemory
+            } else {
+                Self::Terminated
+            }
+        } else if let Some(exception) = scope.exception() {
+            let mut error = Self::from_v8(scope, exception);
+            if let Self::Exception(exception) = &mut error {
+                if exception.stack.is_none() {
+                    exception.stack = scope.stack_trace().map(|stack| {
+                        let stack = stack.to_rust_string_lossy(scope);
+                        crate::source_map::remap_stack(scope, stack)
+                    });
+                }
+            }
+            error
+        } else {
+            Self::new("Error", "execution failed without an exception")
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(exception: wasm_bindgen::JsValue) -> Self {
+        fn property(object: &wasm_bindgen::JsValue, name: &str) -> Option<String> {
+            let value =
+                js_sys::Reflect::get(object, &wasm_bindgen::JsValue::from(name)).ok()?;
+            if value.is_undefined() || value.is_null() {
+                None
+            } else {
+                Some(describe(&value))
+            }
+        }
+
+        fn describe(value: &wasm_bindgen::JsValue) -> String {
+            value
+                .as_string()
+                .unwrap_or_else(|| String::from(js_sys::JsString::from("").concat(value)))
+        }
+
+        Self::Exception(if exception.is_object() {
+            Exception {
+                name: property(&exception, "name").unwrap_or_else(|| "Error".to_owned()),
+                message: property(&exception, "message")
+                    .unwrap_or_else(|| describe(&exception)),
+                stack: property(&exception, "stack"),
+                value: Some(Value::from_web(exception)),
+            }
+        } else {
+            Exception {
+                name: "Error".to_owned(),
+                message: describe(&exception),
+                stack: None,
+                value: Some(Value::from_web(exception)),
+            }
+        })
+    }
+
+    /// The value that was thrown, if the error came from JavaScript.
+    pub fn value(&self) -> Option<&Value> {
+        match self {
+            Self::Exception(exception) => exception.value(),
+            Self::Terminated | Self::OutOfMemory => None,
+        }
+    }
+
+    /// Converts the error into a value that can be thrown back into JavaScript.
+    pub fn to_value(&self, scope: &mut Scope) -> Value {
+        match self {
+            Self::Exception(exception) => exception.to_value(scope),
+            Self::Terminated | Self::OutOfMemory => Value::error(scope, self.message()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Exception(exception) => exception.name(),
+            Self::Terminated => "Error",
+            Self::OutOfMemory => "RangeError",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Exception(exception) => exception.message(),
+            Self::Terminated => "execution was terminated",
+            Self::OutOfMemory => "heap limit exceeded",
+        }
+    }
+
+    pub fn stack(&self) -> Option<&str> {
+        match self {
+            Self::Exception(exception) => exception.stack(),
+            Self::Terminated | Self::OutOfMemory => None,
+        }
+    }
+}
+
+impl Exception {
+    /// The value that was thrown, if the exception came from JavaScript.
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+
+    /// Converts the exception into a value that can be thrown back into
+    /// JavaScript.
+    pub fn to_value(&self, scope: &mut Scope) -> Value {
+        if let Some(value) = &self.value {
+            return value.clone();
+        }
+        let error = Value::error(scope, &self.message);
+        if self.name != "Error" {
+            if let Value::Object(object) = &error {
+                object
+                    .set(scope, "name", Value::String(self.name.clone()))
+                    .ok();
+            }
+        }
+        error
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn stack(&self) -> Option<&str> {
+        self.stack.as_deref()
+    }
+}
+
+impl std::fmt::Debug for JsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exception(exception) => std::fmt::Debug::fmt(exception, f),
+            Self::Terminated => f.write_str("Terminated"),
+            Self::OutOfMemory => f.write_str("OutOfMemory"),
+        }
+    }
+}
+
+impl std::fmt::Display for JsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exception(exception) => std::fmt::Display::fmt(exception, f),
+            Self::Terminated | Self::OutOfMemory => f.write_str(self.message()),
+        }
+    }
+}
+
+impl std::error::Error for JsError {}
+
+impl std::fmt::Debug for Exception {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Exception")
+            .field("name", &self.name)
+            .field("message", &self.message)
+            .field("stack", &self.stack)
+            .finish()
+    }
+}
+
+impl std::fmt::Display for Exception {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.message)
+    }
+}
+
+/// An error converting between Rust data and JavaScript values with serde.
+pub enum SerdeError {
+    /// JavaScript threw while the value was read or built, e.g. from a getter.
+    Exception(JsError),
+    /// The value doesn't fit the Rust type, or the Rust data can't be
+    /// represented in JavaScript.
+    Invalid(String),
+}
+
+impl std::fmt::Debug for SerdeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exception(error) => f.debug_tuple("Exception").field(error).finish(),
+            Self::Invalid(message) => f.debug_tuple("Invalid").field(message).finish(),
+        }
+    }
+}
+
+impl std::fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exception(error) => std::fmt::Display::fmt(error, f),
+            Self::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl serde::ser::Error for SerdeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self::Invalid(msg.to_string())
+    }
+}
+
+impl serde::de::Error for SerdeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self::Invalid(msg.to_string())
+    }
+}
+
+impl From<JsError> for SerdeError {
+    fn from(value: JsError) -> Self {
+        Self::Exception(value)
+    }
+}
@@ -0,0 +1,433 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{collections::HashMap, rc::Rc};
+
+    use crate::{module::script_origin, JsError, ModuleLoader, Object, Promise};
+
+    /// Modules compiled during a single import, stored in an isolate slot so that
+    /// the resolve callback can find them.
+    struct ModuleMap {
+        loader: Rc<dyn ModuleLoader>,
+        modules: HashMap<String, v8::Global<v8::Module>>,
+    }
+
+    impl ModuleMap {
+        /// The name `module` was loaded as. Modules are compared by handle, as
+        /// their identity hashes aren't unique.
+        fn name(&self, module: v8::Local<v8::Module>) -> Option<&str> {
+            self.modules
+                .iter()
+                .find(|(_, global)| module == **global)
+                .map(|(name, _)| name.as_str())
+        }
+    }
+
+    /// Compiles, links and starts evaluating the module graph rooted at
+    /// `specifier`, returning its namespace and the promise that settles once
+    /// evaluation, including any top-level `await`, has finished.
+    pub(crate) fn import(
+        scope: &mut v8::HandleScope,
+        loader: Rc<dyn ModuleLoader>,
+        specifier: &str,
+    ) -> Result<(Object, Promise), JsError> {
+        scope.set_slot(ModuleMap {
+            loader: loader.clone(),
+            modules: HashMap::new(),
+        });
+        let result = {
+            let scope = &mut v8::TryCatch::new(scope);
+            loader
+                .resolve(specifier, "")
+                .and_then(|name| compile(scope, &name))
+                .and_then(|module| {
+                    if module.instantiate_module(scope, resolve).is_none() {
+                        return Err(JsError::from_try_catch(scope));
+                    }
+                    let Some(promise) = module.evaluate(scope) else {
+                        return Err(JsError::from_try_catch(scope));
+                    };
+                    let promise = v8::Local::<v8::Promise>::try_from(promise).unwrap();
+                    let namespace = module.get_module_namespace();
+                    Ok((
+                        Object::from_v8(scope, namespace.try_into().unwrap()),
+                        Promise::from_v8(scope, promise),
+                    ))
+                })
+        };
+        scope.remove_slot::<ModuleMap>();
+        result
+    }
+
+    fn compile<'s>(
+        scope: &mut v8::TryCatch<'s, v8::HandleScope>,
+        name: &str,
+    ) -> Result<v8::Local<'s, v8::Module>, JsError> {
+        let map = scope.get_slot::<ModuleMap>().unwrap();
+        if let Some(module) = map.modules.get(name) {
+            let module = module.clone();
+            return Ok(v8::Local::new(scope, module));
+        }
+        let loader = map.loader.clone();
+        let source = loader.load(name)?;
+
+        let code = v8::String::new(scope, &source).unwrap();
+        let origin = script_origin(scope, name, 0, 0, true);
+        let source = v8::script_compiler::Source::new(code, Some(&origin));
+        let Some(module) = v8::script_compiler::compile_module(scope, source) else {
+            return Err(JsError::from_try_catch(scope));
+        };
+        let global = v8::Global::new(scope, module);
+        let map = scope.get_slot_mut::<ModuleMap>().unwrap();
+        map.modules.insert(name.to_owned(), global);
+
+        let requests = module.get_module_requests();
+        for i in 0..requests.length() {
+            let request = requests.get(scope, i).unwrap();
+            let request = v8::Local::<v8::ModuleRequest>::try_from(request).unwrap();
+            let specifier = request.get_specifier().to_rust_string_lossy(scope);
+            let dependency = loader.resolve(&specifier, name)?;
+            compile(scope, &dependency)?;
+        }
+        Ok(module)
+    }
+
+    fn resolve<'a>(
+        context: v8::Local<'a, v8::Context>,
+        specifier: v8::Local<'a, v8::String>,
+        _import_assertions: v8::Local<'a, v8::FixedArray>,
+        referrer: v8::Local<'a, v8::Module>,
+    ) -> Option<v8::Local<'a, v8::Module>> {
+        let scope = &mut unsafe { v8::CallbackScope::new(context) };
+        let specifier = specifier.to_rust_string_lossy(scope);
+        let map = scope.get_slot::<ModuleMap>()?;
+        let referrer = map.name(referrer)?;
+        let module = map
+            .loader
+            .resolve(&specifier, referrer)
+            .ok()
+            .and_then(|name| map.modules.get(&name))
+            .cloned();
+        match module {
+            Some(module) => Some(v8::Local::new(scope, module)),
+            None => {
+                let message = format!("cannot resolve module '{}'", specifier);
+                let message = v8::String::new(scope, &message).unwrap();
+                let exception = v8::Exception::error(scope, message);
+                scope.throw_exception(exception);
+                None
+            }
+        }
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
+
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use std::{collections::HashMap, rc::Rc};
+
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    use super::specifiers::find_specifiers;
+    use crate::{JsError, ModuleLoader, Object};
+
+    /// Imports the module graph rooted at `specifier`, returning its namespace.
+    ///
+    /// Browsers can only import from URLs, so every module is rewritten to import
+    /// its dependencies from blob URLs. Circular imports are not supported.
+    pub(crate) async fn import(
+        loader: Rc<dyn ModuleLoader>,
+        specifier: &str,
+    ) -> Result<Object, JsError> {
+        let name = loader.resolve(specifier, "")?;
+        let mut urls = HashMap::new();
+        let url = blob_url(&*loader, &name, &mut urls, &mut vec![])?;
+        let import = js_sys::Function::new_with_args("url", "return import(url)");
+        let promise = import
+            .call1(&JsValue::null(), &JsValue::from(&url))
+            .map_err(JsError::from_web)?;
+        let result = JsFuture::from(js_sys::Promise::from(promise)).await;
+        for url in urls.values() {
+            web_sys::Url::revoke_object_url(url).ok();
+        }
+        let namespace = result.map_err(JsError::from_web)?;
+        Ok(Object::from_web(namespace.unchecked_into()))
+    }
+
+    fn blob_url(
+        loader: &dyn ModuleLoader,
+        name: &str,
+        urls: &mut HashMap<String, String>,
+        stack: &mut Vec<String>,
+    ) -> Result<String, JsError> {
+        if let Some(url) = urls.get(name) {
+            return Ok(url.clone());
+        }
+        if stack.iter().any(|entry| entry == name) {
+            return Err(JsError::new(
+                "Error",
+                &format!("circular import of module '{}'", name),
+            ));
+        }
+        stack.push(name.to_owned());
+        let source = loader.load(name)?;
+        let mut rewritten = String::with_capacity(source.len());
+        let mut copied = 0;
+        for (start, end) in find_specifiers(&source) {
+            let dependency = loader.resolve(&source[start..end], name)?;
+            rewritten.push_str(&source[copied..start]);
+            rewritten.push_str(&blob_url(loader, &dependency, urls, stack)?);
+            copied = end;
+        }
+        rewritten.push_str(&source[copied..]);
+        stack.pop();
+
+        let parts = js_sys::Array::of1(&JsValue::from(rewritten));
+        let options = web_sys::BlobPropertyBag::new();
+        options.set_type("text/javascript");
+        let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)
+            .map_err(JsError::from_web)?;
+        let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(JsError::from_web)?;
+        urls.insert(name.to_owned(), url.clone());
+        Ok(url)
+    }
+}
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasm::*;
+
+/// A small tokenizer for import specifiers, used to rewrite them on the web.
+#[cfg(any(target_arch = "wasm32", test))]
+mod specifiers {
+    /// Finds the specifiers of static imports and exports (`import "x"` and
+    /// `from "x"`), returning their byte ranges without quotes. The source is
+    /// tokenized, so strings, comments, template literals and regular
+    /// expressions that happen to contain `import` or `from` are left alone.
+    pub(crate) fn find_specifiers(source: &str) -> Vec<(usize, usize)> {
+        let bytes = source.as_bytes();
+        let mut specifiers = vec![];
+        // the brace depth of each template literal substitution we're inside
+        let mut templates: Vec<usize> = vec![];
+        let mut depth = 0;
+        // whether the previous token was `import` or `from`
+        let mut expects_specifier = false;
+        // whether a `/` here starts a regular expression rather than a division
+        let mut regex_allowed = true;
+        let mut i = 0;
+        while i < bytes.len() {
+            let byte = bytes[i];
+            if byte.is_ascii_whitespace() {
+                i += 1;
+                continue;
+            }
+            if bytes[i..].starts_with(b"//") {
+                i = find_from(bytes, i, b"\n").unwrap_or(bytes.len());
+                continue;
+            }
+            if bytes[i..].starts_with(b"/*") {
+                i = find_from(bytes, i + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+                continue;
+            }
+            let is_specifier = expects_specifier;
+            expects_specifier = false;
+            match byte {
+                b'"' | b'\'' => {
+                    let end = skip_string(bytes, i);
+                    let closed = end > i + 1 && bytes[end - 1] == byte;
+                    if is_specifier && closed {
+                        specifiers.push((i + 1, end - 1));
+                    }
+                    i = end;
+                    regex_allowed = false;
+                }
+                b'`' => {
+                    i = skip_template(bytes, i + 1, &mut templates, depth);
+                    regex_allowed = false;
+                }
+                b'}' if templates.last() == Some(&depth) => {
+                    templates.pop();
+                    i = skip_template(bytes, i + 1, &mut templates, depth);
+                    regex_allowed = false;
+                }
+                b'/' if regex_allowed => {
+                    i = skip_regex(bytes, i + 1);
+                    regex_allowed = false;
+                }
+                _ if is_identifier(byte) => {
+                    let start = i;
+                    while i < bytes.len() && is_identifier(bytes[i]) {
+                        i += 1;
+                    }
+                    let word = &source[start..i];
+                    let after_dot = source[..start].trim_end().ends_with('.');
+                    expects_specifier = !after_dot && (word == "import" || word == "from");
+                    regex_allowed = !after_dot
+                        && matches!(
+                            word,
+                            "return" | "typeof" | "instanceof" | "in" | "of" | "new" | "delete"
+                                | "void" | "throw" | "case" | "do" | "else" | "yield" | "await"
+                        );
+                }
+                _ => {
+                    match byte {
+                        b'{' => depth += 1,
+                        b'}' => depth = depth.saturating_sub(1),
+                        _ => {}
+                    }
+                    regex_allowed = !matches!(byte, b')' | b']' | b'}');
+                    i += 1;
+                }
+            }
+        }
+        specifiers
+    }
+
+    fn is_identifier(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || byte >= 0x80
+    }
+
+    fn find_from(bytes: &[u8], start: usize, needle: &[u8]) -> Option<usize> {
+        bytes[start..]
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .map(|index| start + index)
+    }
+
+    /// Skips a quoted string starting at `start`, returning the index after
+    /// its closing quote.
+    fn skip_string(bytes: &[u8], start: usize) -> usize {
+        let quote = bytes[start];
+        let mut i = start + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'\n' => return i,
+                byte if byte == quote => return i + 1,
+                _ => i += 1,
+            }
+        }
+        bytes.len()
+    }
+
+    /// Skips template literal text starting at `start`, up to and including
+    /// the closing backtick or the `${` of a substitution. Substitutions are
+    /// code, so they are recorded in `templates` and tokenized as usual.
+    fn skip_template(
+        bytes: &[u8],
+        start: usize,
+        templates: &mut Vec<usize>,
+        depth: usize,
+    ) -> usize {
+        let mut i = start;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'`' => return i + 1,
+                b'$' if bytes.get(i + 1) == Some(&b'{') => {
+                    templates.push(depth);
+                    return i + 2;
+                }
+                _ => i += 1,
+            }
+        }
+        bytes.len()
+    }
+
+    /// Skips a regular expression literal whose body starts at `start`,
+    /// returning the index after its flags.
+    fn skip_regex(bytes: &[u8], start: usize) -> usize {
+        let mut i = start;
+        let mut in_class = false;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'\n' => return i,
+                b'[' => {
+                    in_class = true;
+                    i += 1;
+                }
+                b']' => {
+                    in_class = false;
+                    i += 1;
+                }
+                b'/' if !in_class => {
+                    i += 1;
+                    while i < bytes.len() && is_identifier(bytes[i]) {
+                        i += 1;
+                    }
+                    return i;
+                }
+                _ => i += 1,
+            }
+        }
+        bytes.len()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::find_specifiers;
+
+        fn specifiers(source: &str) -> Vec<&str> {
+            find_specifiers(source)
+                .into_iter()
+                .map(|(start, end)| &source[start..end])
+                .collect()
+        }
+
+        #[test]
+        fn finds_static_imports_and_exports() {
+            let source = r#"
+                import "./side-effect.js";
+                import a, { b } from './a.js';
+                import * as c from "./c.js";
+                export { d } from "./d.js";
+                export * from './e.js';
+            "#;
+            assert_eq!(
+                specifiers(source),
+                ["./side-effect.js", "./a.js", "./c.js", "./d.js", "./e.js"]
+            );
+        }
+
+        #[test]
+        fn skips_strings_and_comments() {
+            let source = r#"
+                // import "./line.js";
+                /* import x from "./block.js"; */
+                const text = 'import "./single.js"';
+                const other = "from './double.js'";
+                import y from "./real.js";
+            "#;
+            assert_eq!(specifiers(source), ["./real.js"]);
+        }
+
+        #[test]
+        fn skips_template_literals() {
+            let source = r#"
+                const a = `import "./template.js"`;
+                const b = `${ { key: `from "./nested.js"` }.key } import "./text.js"`;
+                import z from "./real.js";
+            "#;
+            assert_eq!(specifiers(source), ["./real.js"]);
+        }
+
+        #[test]
+        fn skips_regular_expressions() {
+            let source = r#"
+                const pattern = /import "\.\/regex.js"/g;
+                const ratio = width / height / 2;
+                import w from "./real.js";
+            "#;
+            assert_eq!(specifiers(source), ["./real.js"]);
+        }
+
+        #[test]
+        fn ignores_properties_named_like_keywords() {
+            let source = r#"
+                const items = Array.from("abc");
+                loader.import("./dynamic.js");
+            "#;
+            assert!(specifiers(source).is_empty());
+        }
+    }
+}
@@ -0,0 +1,336 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{
+        collections::BTreeMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Condvar, Mutex, OnceLock,
+        },
+        task::{Context, Poll, Wake, Waker},
+        thread::Thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        task::{self, HostTasks},
+        JsError,
+    };
+
+    struct Timer {
+        deadline: Instant,
+        interval: Option<Duration>,
+        context: v8::Global<v8::Context>,
+        callback: v8::Global<v8::Function>,
+        args: Vec<v8::Global<v8::Value>>,
+    }
+
+    /// Timers scheduled by scripts. Stored in an isolate slot.
+    #[derive(Default)]
+    pub(crate) struct EventLoop {
+        timers: BTreeMap<u32, Timer>,
+        next_id: u32,
+        wakeup: Option<Wakeup>,
+    }
+
+    impl EventLoop {
+        pub(crate) fn has_pending_work(&self) -> bool {
+            !self.timers.is_empty()
+        }
+    }
+
+    impl Drop for EventLoop {
+        fn drop(&mut self) {
+            if let Some(wakeup) = self.wakeup.take() {
+                wakeup.cancel();
+            }
+        }
+    }
+
+    /// Wakes event loops once their next timer is due. A single thread serves
+    /// every runtime, sleeping until the earliest deadline.
+    struct TimerThread {
+        wakeups: Mutex<BTreeMap<(Instant, u64), Waker>>,
+        condvar: Condvar,
+        next_id: AtomicU64,
+    }
+
+    impl TimerThread {
+        fn get() -> &'static Self {
+            static TIMER_THREAD: OnceLock<TimerThread> = OnceLock::new();
+            let mut spawn = false;
+            let timer_thread = TIMER_THREAD.get_or_init(|| {
+                spawn = true;
+                TimerThread {
+                    wakeups: Mutex::new(BTreeMap::new()),
+                    condvar: Condvar::new(),
+                    next_id: AtomicU64::new(0),
+                }
+            });
+            if spawn {
+                std::thread::spawn(move || timer_thread.run());
+            }
+            timer_thread
+        }
+
+        fn run(&self) {
+            let mut wakeups = self.wakeups.lock().unwrap();
+            loop {
+                let now = Instant::now();
+                match wakeups.first_key_value().map(|(key, _)| *key) {
+                    Some(key) if key.0 <= now => {
+                        let waker = wakeups.remove(&key).unwrap();
+                        drop(wakeups);
+                        waker.wake();
+                        wakeups = self.wakeups.lock().unwrap();
+                    }
+                    Some((deadline, _)) => {
+                        wakeups = self.condvar.wait_timeout(wakeups, deadline - now).unwrap().0;
+                    }
+                    None => wakeups = self.condvar.wait(wakeups).unwrap(),
+                }
+            }
+        }
+    }
+
+    /// A pending wake-up on the timer thread.
+    struct Wakeup {
+        deadline: Instant,
+        id: u64,
+        waker: Waker,
+    }
+
+    impl Wakeup {
+        fn schedule(deadline: Instant, waker: Waker) -> Self {
+            let timer_thread = TimerThread::get();
+            let id = timer_thread.next_id.fetch_add(1, Ordering::Relaxed);
+            timer_thread
+                .wakeups
+                .lock()
+                .unwrap()
+                .insert((deadline, id), waker.clone());
+            timer_thread.condvar.notify_one();
+            Self {
+                deadline,
+                id,
+                waker,
+            }
+        }
+
+        fn cancel(self) {
+            let timer_thread = TimerThread::get();
+            timer_thread
+                .wakeups
+                .lock()
+                .unwrap()
+                .remove(&(self.deadline, self.id));
+        }
+    }
+
+    /// Installs `setTimeout`, `setInterval`, `clearTimeout`, `clearInterval` and
+    /// `queueMicrotask` on the global object.
+    pub(crate) fn install(scope: &mut v8::HandleScope) {
+        fn define(
+            scope: &mut v8::HandleScope,
+            name: &str,
+            callback: impl v8::MapFnTo<v8::FunctionCallback>,
+        ) {
+            let global = scope.get_current_context().global(scope);
+            let name = v8::String::new(scope, name).unwrap();
+            let function = v8::Function::new(scope, callback).unwrap();
+            global.set(scope, name.into(), function.into());
+        }
+
+        define(scope, "setTimeout", set_timeout);
+        define(scope, "setInterval", set_interval);
+        define(scope, "clearTimeout", clear_timer);
+        define(scope, "clearInterval", clear_timer);
+        define(scope, "queueMicrotask", queue_microtask);
+    }
+
+    fn set_timeout(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        ret: v8::ReturnValue,
+    ) {
+        schedule(scope, args, ret, false);
+    }
+
+    fn set_interval(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        ret: v8::ReturnValue,
+    ) {
+        schedule(scope, args, ret, true);
+    }
+
+    fn schedule(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        mut ret: v8::ReturnValue,
+        repeat: bool,
+    ) {
+        let Ok(callback) = v8::Local::<v8::Function>::try_from(args.get(0)) else {
+            let message = v8::String::new(scope, "callback must be a function").unwrap();
+            let exception = v8::Exception::type_error(scope, message);
+            scope.throw_exception(exception);
+            return;
+        };
+        let delay = args
+            .get(1)
+            .number_value(scope)
+            .filter(|delay| delay.is_finite())
+            .unwrap_or(0.0)
+            .max(if repeat { 1.0 } else { 0.0 });
+        let delay = Duration::from_secs_f64(delay / 1000.0);
+        let timer = Timer {
+            deadline: Instant::now() + delay,
+            interval: repeat.then_some(delay),
+            context: {
+                let context = scope.get_current_context();
+                v8::Global::new(scope, context)
+            },
+            callback: v8::Global::new(scope, callback),
+            args: (2..args.length())
+                .map(|i| v8::Global::new(scope, args.get(i)))
+                .collect(),
+        };
+        let event_loop = scope.get_slot_mut::<EventLoop>().unwrap();
+        // ids start at 1 so that they are always truthy, and once they wrap
+        // around, skip those of timers that are still pending
+        loop {
+            event_loop.next_id = event_loop.next_id.wrapping_add(1).max(1);
+            if !event_loop.timers.contains_key(&event_loop.next_id) {
+                break;
+            }
+        }
+        let id = event_loop.next_id;
+        event_loop.timers.insert(id, timer);
+        ret.set(v8::Integer::new_from_unsigned(scope, id).into());
+    }
+
+    fn clear_timer(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        _ret: v8::ReturnValue,
+    ) {
+        if let Some(id) = args.get(0).uint32_value(scope) {
+            scope.get_slot_mut::<EventLoop>().unwrap().timers.remove(&id);
+        }
+    }
+
+    fn queue_microtask(
+        scope: &mut v8::HandleScope,
+        args: v8::FunctionCallbackArguments,
+        _ret: v8::ReturnValue,
+    ) {
+        if let Ok(callback) = v8::Local::<v8::Function>::try_from(args.get(0)) {
+            scope.enqueue_microtask(callback);
+        } else {
+            let message = v8::String::new(scope, "callback must be a function").unwrap();
+            let exception = v8::Exception::type_error(scope, message);
+            scope.throw_exception(exception);
+        }
+    }
+
+    /// Runs one turn of the event loop: polls async host functions, runs due
+    /// timers and performs a microtask checkpoint. Returns `Ready` once there is
+    /// no pending work left, or with the first exception thrown by a timer.
+    pub(crate) fn poll(
+        scope: &mut v8::HandleScope,
+        cx: &mut Context,
+    ) -> Poll<Result<(), JsError>> {
+        let tasks_pending = task::poll(scope, cx);
+        scope.perform_microtask_checkpoint();
+        if let Err(err) = run_timers(scope) {
+            return Poll::Ready(Err(err));
+        }
+        if scope
+            .get_slot::<HostTasks>()
+            .is_some_and(|host_tasks| host_tasks.has_unpolled())
+        {
+            // poll again straight away, so that new tasks can register their wakers
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let Some(event_loop) = scope.get_slot_mut::<EventLoop>() else {
+            return Poll::Ready(Ok(()));
+        };
+        let now = Instant::now();
+        let next_deadline = event_loop.timers.values().map(|timer| timer.deadline).min();
+        // an earlier wake-up that hasn't fired yet polls again in time
+        let scheduled = event_loop.wakeup.as_ref().is_some_and(|wakeup| {
+            next_deadline.is_some_and(|deadline| wakeup.deadline <= deadline)
+                && wakeup.deadline > now
+                && wakeup.waker.will_wake(cx.waker())
+        });
+        if !scheduled {
+            if let Some(wakeup) = event_loop.wakeup.take() {
+                wakeup.cancel();
+            }
+            if let Some(deadline) = next_deadline {
+                event_loop.wakeup = Some(Wakeup::schedule(deadline, cx.waker().clone()));
+            }
+        }
+        if next_deadline.is_some() || tasks_pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn run_timers(scope: &mut v8::HandleScope) -> Result<(), JsError> {
+        let now = Instant::now();
+        let Some(event_loop) = scope.get_slot::<EventLoop>() else {
+            return Ok(());
+        };
+        let mut due = event_loop
+            .timers
+            .iter()
+            .filter(|(_, timer)| timer.deadline <= now)
+            .map(|(id, timer)| (timer.deadline, *id))
+            .collect::<Vec<_>>();
+        due.sort();
+        for (_, id) in due {
+            // an earlier callback may have cleared this timer
+            let event_loop = scope.get_slot_mut::<EventLoop>().unwrap();
+            let Some(timer) = event_loop.timers.get_mut(&id) else {
+                continue;
+            };
+            let context = timer.context.clone();
+            let callback = timer.callback.clone();
+            let args = timer.args.clone();
+            if let Some(interval) = timer.interval {
+                timer.deadline = now + interval;
+            } else {
+                event_loop.timers.remove(&id);
+            }
+
+            let context = v8::Local::new(scope, context);
+            let scope = &mut v8::ContextScope::new(scope, context);
+            let scope = &mut v8::TryCatch::new(scope);
+            let callback = v8::Local::new(scope, callback);
+            let args = args
+                .iter()
+                .map(|arg| v8::Local::new(scope, arg))
+                .collect::<Vec<_>>();
+            let recv = v8::undefined(scope);
+            if callback.call(scope, recv.into(), &args).is_none() {
+                return Err(JsError::from_try_catch(scope));
+            }
+            scope.perform_microtask_checkpoint();
+        }
+        Ok(())
+    }
+
+    /// Wakes a thread that is parked while waiting on the event loop.
+    pub(crate) struct ThreadWaker(pub(crate) Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
@@ -0,0 +1,105 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::collections::HashMap;
+
+    /// Builtins that the engine has no direct API for, by their path from the
+    /// global object.
+    const PATHS: &[&str] = &[
+        "Object.preventExtensions",
+        "Object.isExtensible",
+        "Object.isFrozen",
+        "Object.isSealed",
+        "Array.prototype.splice",
+        "Array.prototype.slice",
+    ];
+
+    /// The builtins in [`PATHS`] as they were when a context was created, so
+    /// that scripts replacing them don't change how Rust sees values. Stored
+    /// in a context slot.
+    struct Intrinsics(HashMap<&'static str, v8::Global<v8::Function>>);
+
+    /// Captures the current context's builtins, before any script runs.
+    pub(crate) fn install(scope: &mut v8::HandleScope) {
+        let mut intrinsics = HashMap::new();
+        for path in PATHS {
+            if let Some(function) = lookup(scope, path) {
+                intrinsics.insert(*path, v8::Global::new(scope, function));
+            }
+        }
+        let context = scope.get_current_context();
+        context.set_slot(scope, Intrinsics(intrinsics));
+    }
+
+    /// Calls the builtin at `path`, such as `Object.isFrozen`, which must be in
+    /// [`PATHS`].
+    pub(crate) fn call<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        path: &'static str,
+        this: v8::Local<v8::Value>,
+        args: &[v8::Local<v8::Value>],
+    ) -> Option<v8::Local<'s, v8::Value>> {
+        let context = scope.get_current_context();
+        let function = context
+            .get_slot::<Intrinsics>(scope)
+            .and_then(|intrinsics| intrinsics.0.get(path).cloned());
+        // contexts the runtime didn't create have no slot
+        let function = match function {
+            Some(function) => v8::Local::new(scope, function),
+            None => lookup(scope, path)?,
+        };
+        function.call(scope, this, args)
+    }
+
+    fn lookup<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        path: &str,
+    ) -> Option<v8::Local<'s, v8::Function>> {
+        let mut value: v8::Local<v8::Value> = scope.get_current_context().global(scope).into();
+        for name in path.split('.') {
+            let object: v8::Local<v8::Object> = value.try_into().ok()?;
+            let name = v8::String::new(scope, name)?;
+            value = object.get(scope, name.into())?;
+        }
+        value.try_into().ok()
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
+
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use wasm_bindgen::{JsCast, JsValue};
+
+    thread_local! {
+        /// Builtins by their path from the global object, as they were when
+        /// first called. Scripts share the page's global object, so there is no
+        /// earlier point to capture them at.
+        static INTRINSICS: RefCell<HashMap<&'static str, js_sys::Function>> =
+            RefCell::new(HashMap::new());
+    }
+
+    /// Calls the builtin at `path`, such as `Array.prototype.splice`.
+    pub(crate) fn call(
+        path: &'static str,
+        this: &JsValue,
+        args: &js_sys::Array,
+    ) -> Result<JsValue, JsValue> {
+        let function = INTRINSICS.with(|intrinsics| {
+            if let Some(function) = intrinsics.borrow().get(path) {
+                return Ok(function.clone());
+            }
+            let mut value: JsValue = js_sys::global().into();
+            for name in path.split('.') {
+                value = js_sys::Reflect::get(&value, &JsValue::from_str(name))?;
+            }
+            let function: js_sys::Function = value.dyn_into()?;
+            intrinsics.borrow_mut().insert(path, function.clone());
+            Ok::<_, JsValue>(function)
+        })?;
+        js_sys::Reflect::apply(&function, this, args)
+    }
+}
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasm::*;
@@ -1,11 +1,42 @@
-// TODO:
-// handle throw in functions (they should return results)
-// more array functions
-// errors (remove unwraps)
-// pass extra data to functions?
-
+mod bigint;
+mod buffer;
+mod collection;
+mod commonjs;
+mod console;
+mod date;
+mod de;
+mod error;
+mod es_module;
+mod event_loop;
+mod intrinsics;
+mod loader;
 mod value;
 mod module;
+mod promise;
+mod runtime;
+mod script;
+mod ser;
+mod snapshot;
+mod source_map;
+mod string;
+mod symbol;
+mod task;
+mod termination;
 
+pub use bigint::*;
+pub use buffer::*;
+pub use collection::*;
+pub use console::*;
+pub use date::*;
+pub use error::*;
+pub use loader::*;
 pub use value::*;
 pub use module::*;
+pub use promise::*;
+pub use runtime::*;
+pub use script::*;
+pub use snapshot::*;
+pub use source_map::*;
+pub use string::*;
+pub use symbol::*;
+pub use termination::*;
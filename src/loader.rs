@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use crate::JsError;
+
+/// Resolves module specifiers to source text.
+pub trait ModuleLoader {
+    /// Resolves `specifier`, as imported by the module named `referrer`, to the
+    /// canonical name of a module. `referrer` is empty for the entry module.
+    fn resolve(&self, specifier: &str, referrer: &str) -> Result<String, JsError> {
+        Ok(resolve_path(specifier, referrer))
+    }
+
+    /// Loads the source text of a resolved module.
+    fn load(&self, name: &str) -> Result<String, JsError>;
+}
+
+/// Joins a relative specifier onto the directory of `referrer`. Bare and absolute
+/// specifiers are returned as-is, apart from normalization.
+pub fn resolve_path(specifier: &str, referrer: &str) -> String {
+    let mut parts: Vec<&str> = vec![];
+    if specifier.starts_with("./") || specifier.starts_with("../") {
+        parts.extend(referrer.split('/'));
+        parts.pop();
+    }
+    let absolute = if parts.is_empty() {
+        specifier.starts_with('/')
+    } else {
+        referrer.starts_with('/')
+    };
+    for part in specifier.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if matches!(parts.last(), None | Some(&"..")) {
+                    if !absolute {
+                        parts.push("..");
+                    }
+                } else {
+                    parts.pop();
+                }
+            }
+            part => parts.push(part),
+        }
+    }
+    let path = parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    if absolute {
+        format!("/{}", path)
+    } else {
+        path
+    }
+}
+
+/// Serves modules from memory, keyed by name.
+#[derive(Default, Clone)]
+pub struct MemoryModuleLoader {
+    modules: HashMap<String, String>,
+}
+
+impl MemoryModuleLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: &str, source: &str) {
+        self.modules
+            .insert(resolve_path(name, ""), source.to_owned());
+    }
+
+    pub fn with(mut self, name: &str, source: &str) -> Self {
+        self.insert(name, source);
+        self
+    }
+}
+
+impl ModuleLoader for MemoryModuleLoader {
+    fn load(&self, name: &str) -> Result<String, JsError> {
+        self.modules
+            .get(name)
+            .cloned()
+            .ok_or_else(|| JsError::new("Error", &format!("cannot find module '{}'", name)))
+    }
+}
+
+/// Serves modules from a directory on disk. Specifiers are resolved relative to
+/// the directory, and may not escape it.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
+pub struct FsModuleLoader {
+    root: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FsModuleLoader {
+    pub fn new(root: impl Into<std::path::PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ModuleLoader for FsModuleLoader {
+    fn resolve(&self, specifier: &str, referrer: &str) -> Result<String, JsError> {
+        let name = resolve_path(specifier, referrer);
+        let name = name.trim_start_matches('/');
+        if name.starts_with("..") {
+            return Err(JsError::new(
+                "Error",
+                &format!("module '{}' is outside of the module root", specifier),
+            ));
+        }
+        Ok(name.to_owned())
+    }
+
+    fn load(&self, name: &str) -> Result<String, JsError> {
+        std::fs::read_to_string(self.root.join(name))
+            .map_err(|err| JsError::new("Error", &format!("cannot load module '{}': {}", name, err)))
+    }
+}
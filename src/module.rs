@@ -1,50 +1,109 @@
-#[cfg(not(target_arch = "wasm32"))]
-mod native {
-    use crate::Object;
+use crate::{Context, JsError, Runtime, SourceMap, Value};
+
+/// Where a script's source comes from, as shown in stack traces.
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    /// The file name of the script.
+    pub name: String,
+    /// The line the script starts on, if it is embedded in a larger file.
+    pub line_offset: i32,
+    /// The column the script's first line starts on.
+    pub column_offset: i32,
+    /// Points stack traces at the original source the script was generated
+    /// from. On the web, stack traces are left as they are.
+    pub source_map: Option<SourceMap>,
+}
 
-    pub fn init() {
-        let platform = v8::new_default_platform(0, false).make_shared();
-        v8::V8::initialize_platform(platform);
-        v8::V8::initialize();
+impl LoadOptions {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            line_offset: 0,
+            column_offset: 0,
+            source_map: None,
+        }
     }
+}
 
-    pub struct Module {
-        _private: (),
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self::new("main.js")
     }
+}
 
-    impl Module {
-        pub fn load<'a, 'b, 'c>(js: &'c str) -> (Scope<'a, 'b>, Object) {
-            let mut isolate = v8::Isolate::new(v8::CreateParams::default());
-            let exports = {
-                let handle_scope = &mut v8::HandleScope::new(&mut isolate);
-                let context = v8::Context::new(handle_scope);
-                let scope: &mut v8::ContextScope<v8::HandleScope<_>> =
-                    &mut v8::ContextScope::new(handle_scope, context);
-                let exports = v8::Object::new(scope);
-                let exports_key = v8::String::new(scope, "exports").unwrap();
-                context
-                    .global(scope)
-                    .set(scope, exports_key.into(), exports.into());
-                let code = v8::String::new(scope, &js).unwrap();
-                let script = v8::Script::compile(scope, code, None).unwrap();
-                script.run(scope).unwrap();
-                Object::from_v8(scope, exports)
-            };
-            unsafe {
-                isolate.exit();
-            }
-            (Scope(InnerScope::Isolate(isolate)), exports)
-        }
+/// A script loaded into a [`Runtime`], along with the context it runs in.
+pub struct Module {
+    pub(crate) context: Context,
+    pub(crate) exports: Value,
+}
+
+impl Module {
+    /// Loads a script into its own [`Runtime`], which is owned by the returned
+    /// scope. See [`Runtime::load`].
+    pub fn load<'a, 'b, 'c>(js: &'c str) -> Result<(Scope<'a, 'b>, Value), JsError> {
+        let mut runtime = Runtime::new();
+        let module = runtime.load(js)?;
+        Ok((Scope::owned(runtime, module.context), module.exports))
+    }
+
+    /// Like [`Module::load`], with `name` as the script's file name.
+    pub fn load_named<'a, 'b, 'c>(
+        name: &str,
+        js: &'c str,
+    ) -> Result<(Scope<'a, 'b>, Value), JsError> {
+        Self::load_with_options(js, &LoadOptions::new(name))
+    }
+
+    /// Like [`Module::load`], with the script's origin set by `options`.
+    pub fn load_with_options<'a, 'b, 'c>(
+        js: &'c str,
+        options: &LoadOptions,
+    ) -> Result<(Scope<'a, 'b>, Value), JsError> {
+        let mut runtime = Runtime::new();
+        let module = runtime.load_with_options(js, options)?;
+        Ok((Scope::owned(runtime, module.context), module.exports))
+    }
+
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    pub fn exports(&self) -> &Value {
+        &self.exports
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use crate::{Context, JsError, LoadOptions, Runtime, Value};
+
+    /// Initializes the engine. Calling it again does nothing.
+    pub fn init() {
+        static INIT: std::sync::Once = std::sync::Once::new();
+        INIT.call_once(|| {
+            let platform = v8::new_default_platform(0, false).make_shared();
+            v8::V8::initialize_platform(platform);
+            v8::V8::initialize();
+        });
     }
 
     pub struct Scope<'a, 'b>(pub(crate) InnerScope<'a, 'b>);
 
     pub(crate) enum InnerScope<'a, 'b> {
-        Isolate(v8::OwnedIsolate),
+        Owned(Runtime, v8::Global<v8::Context>),
+        Runtime(&'a mut Runtime, v8::Global<v8::Context>),
         Scope(&'a mut v8::HandleScope<'b>),
     }
 
     impl<'a, 'b> Scope<'a, 'b> {
+        pub(crate) fn owned(runtime: Runtime, context: Context) -> Self {
+            Self(InnerScope::Owned(runtime, context.0))
+        }
+
+        pub(crate) fn runtime(runtime: &'a mut Runtime, context: Context) -> Self {
+            Self(InnerScope::Runtime(runtime, context.0))
+        }
+
         pub(crate) fn scope(scope: &'a mut v8::HandleScope<'b>) -> Self {
             Self(InnerScope::Scope(scope))
         }
@@ -54,36 +113,66 @@ mod native {
             F: FnOnce(&mut v8::HandleScope<v8::Context>) -> R,
         {
             match &mut self.0 {
-                InnerScope::Isolate(isolate) => {
-                    unsafe {
-                        isolate.enter();
-                    }
-                    let result = {
-                        let handle_scope = &mut v8::HandleScope::new(isolate);
-                        let context = v8::Context::new(handle_scope);
-                        let scope: &mut v8::ContextScope<v8::HandleScope<_>> =
-                            &mut v8::ContextScope::new(handle_scope, context);
-                        f(scope)
-                    };
-                    unsafe {
-                        isolate.exit();
-                    }
-                    result
-                }
+                InnerScope::Owned(runtime, context) => runtime.enter(context, f),
+                InnerScope::Runtime(runtime, context) => runtime.enter(context, f),
                 InnerScope::Scope(scope) => f(*scope),
             }
         }
-    }
 
-    impl<'a, 'b> Drop for Scope<'a, 'b> {
-        fn drop(&mut self) {
-            if let InnerScope::Isolate(isolate) = &mut self.0 {
-                unsafe {
-                    isolate.enter();
+        pub(crate) fn try_enter<F, R>(&mut self, f: F) -> Result<R, JsError>
+        where
+            F: FnOnce(&mut v8::HandleScope<v8::Context>) -> Option<R>,
+        {
+            self.enter(|scope| {
+                let scope = &mut v8::TryCatch::new(scope);
+                match f(scope) {
+                    Some(result) if !scope.has_caught() => Ok(result),
+                    _ => Err(JsError::from_try_catch(scope)),
                 }
-            }
+            })
+        }
+
+        /// Runs a classic script, returning its completion value. The source
+        /// map in `options` is ignored.
+        pub(crate) fn eval(&mut self, js: &str, options: &LoadOptions) -> Result<Value, JsError> {
+            self.try_enter(|scope| {
+                let code = v8::String::new(scope, js)?;
+                let origin = script_origin(
+                    scope,
+                    &options.name,
+                    options.line_offset,
+                    options.column_offset,
+                    false,
+                );
+                let script = v8::Script::compile(scope, code, Some(&origin))?;
+                let value = script.run(scope)?;
+                Some(Value::from_v8(scope, value))
+            })
         }
     }
+
+    pub(crate) fn script_origin<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        name: &str,
+        line_offset: i32,
+        column_offset: i32,
+        is_module: bool,
+    ) -> v8::ScriptOrigin<'s> {
+        let resource_name = v8::String::new(scope, name).unwrap();
+        let source_map_url = v8::undefined(scope);
+        v8::ScriptOrigin::new(
+            scope,
+            resource_name.into(),
+            line_offset,
+            column_offset,
+            false,
+            0,
+            source_map_url.into(),
+            false,
+            false,
+            is_module,
+        )
+    }
 }
 #[cfg(not(target_arch = "wasm32"))]
 pub use native::*;
@@ -92,36 +181,10 @@ pub use native::*;
 mod wasm {
     use std::marker::PhantomData;
 
-    use js_sys::{eval, Reflect};
-    use wasm_bindgen::JsValue;
-    use web_sys::{console, window};
-
-    use crate::Object;
+    use crate::{Context, JsError, LoadOptions, Runtime, Value};
 
     pub fn init() {}
 
-    pub struct Module {
-        _private: (),
-    }
-
-    impl Module {
-        pub fn load<'a, 'b, 'c>(js: &'c str) -> (Scope<'a, 'b>, Object) {
-            let exports = js_sys::Object::new();
-            Reflect::set(
-                &window().unwrap().into(),
-                &JsValue::from("exports"),
-                &exports,
-            )
-            .unwrap();
-            if let Err(err) = eval(js) {
-                console::error_1(&err);
-                panic!();
-            }
-            Reflect::delete_property(&window().unwrap().into(), &JsValue::from("exports")).unwrap();
-            (Scope::new(), Object::from_web(exports))
-        }
-    }
-
     pub struct Scope<'a, 'b> {
         _a: PhantomData<&'a ()>,
         _b: PhantomData<&'b ()>,
@@ -134,6 +197,18 @@ mod wasm {
                 _b: PhantomData,
             }
         }
+
+        pub(crate) fn owned(_runtime: Runtime, _context: Context) -> Self {
+            Self::new()
+        }
+
+        /// Runs a classic script, returning its completion value. Only the name
+        /// in `options` is used.
+        pub(crate) fn eval(&mut self, js: &str, options: &LoadOptions) -> Result<Value, JsError> {
+            js_sys::eval(&format!("{}\n//# sourceURL={}", js, options.name))
+                .map(Value::from_web)
+                .map_err(JsError::from_web)
+        }
     }
 }
 #[cfg(target_arch = "wasm32")]
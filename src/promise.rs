@@ -0,0 +1,193 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{JsError, Scope, Value};
+
+#[derive(Debug, Clone)]
+pub enum PromiseState {
+    Pending,
+    Fulfilled(Value),
+    Rejected(Value),
+}
+
+#[derive(Clone)]
+pub struct Promise {
+    #[cfg(not(target_arch = "wasm32"))]
+    promise: v8::Global<v8::Promise>,
+    #[cfg(target_arch = "wasm32")]
+    promise: js_sys::Promise,
+    /// Browsers can't inspect a promise synchronously, so its state is
+    /// recorded once it's awaited. Attaching a handler any earlier would mark
+    /// rejections as handled.
+    #[cfg(target_arch = "wasm32")]
+    state: std::rc::Rc<std::cell::RefCell<PromiseState>>,
+}
+
+impl Promise {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        promise: v8::Local<'b, v8::Promise>,
+    ) -> Self {
+        Self {
+            promise: v8::Global::new(scope, promise),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(&self, scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::Promise> {
+        v8::Local::new(scope, &self.promise)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web<'s>(promise: js_sys::Promise) -> Self {
+        Self {
+            promise,
+            state: std::rc::Rc::new(std::cell::RefCell::new(PromiseState::Pending)),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web<'s>(&self) -> js_sys::Promise {
+        self.promise.clone()
+    }
+
+    /// Inspects the promise without waiting for it. On the web, a promise is
+    /// only seen to settle once it has been awaited with
+    /// [`Promise::into_future`].
+    #[allow(unused_variables)]
+    pub fn state(&self, scope: &mut Scope) -> PromiseState {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let promise = self.promise.clone();
+            scope.enter(move |scope| {
+                let promise = v8::Local::new(scope, promise);
+                match promise.state() {
+                    v8::PromiseState::Pending => PromiseState::Pending,
+                    v8::PromiseState::Fulfilled => {
+                        let value = promise.result(scope);
+                        PromiseState::Fulfilled(Value::from_v8(scope, value))
+                    }
+                    v8::PromiseState::Rejected => {
+                        let value = promise.result(scope);
+                        PromiseState::Rejected(Value::from_v8(scope, value))
+                    }
+                }
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.state.borrow().clone()
+        }
+    }
+
+    /// Waits for the promise to settle, running the event loop in the meantime.
+    /// A rejection, or an exception thrown by a timer, is returned as a
+    /// [`JsError`].
+    pub fn into_future<'s, 'a, 'b>(self, scope: &'s mut Scope<'a, 'b>) -> PromiseFuture<'s, 'a, 'b> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            PromiseFuture {
+                promise: self,
+                scope,
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            PromiseFuture {
+                promise: self,
+                future: None,
+                _scope: std::marker::PhantomData,
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Promise {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[promise]")
+    }
+}
+
+impl From<Promise> for Value {
+    fn from(value: Promise) -> Self {
+        Value::Promise(value)
+    }
+}
+
+/// A future that resolves once a [`Promise`] settles.
+pub struct PromiseFuture<'s, 'a, 'b> {
+    #[cfg(not(target_arch = "wasm32"))]
+    promise: Promise,
+    #[cfg(not(target_arch = "wasm32"))]
+    scope: &'s mut Scope<'a, 'b>,
+    #[cfg(target_arch = "wasm32")]
+    promise: Promise,
+    /// Created when first polled, as it attaches handlers to the promise.
+    #[cfg(target_arch = "wasm32")]
+    future: Option<wasm_bindgen_futures::JsFuture>,
+    #[cfg(target_arch = "wasm32")]
+    _scope: std::marker::PhantomData<&'s mut Scope<'a, 'b>>,
+}
+
+impl<'s, 'a, 'b> Future for PromiseFuture<'s, 'a, 'b> {
+    type Output = Result<Value, JsError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let promise = this.promise.promise.clone();
+            let result = this.scope.enter(move |scope| {
+                let event_loop = crate::event_loop::poll(scope, cx);
+                let promise = v8::Local::new(scope, promise);
+                match promise.state() {
+                    v8::PromiseState::Pending => match event_loop {
+                        Poll::Pending => None,
+                        Poll::Ready(Err(err)) => Some(Err(err)),
+                        Poll::Ready(Ok(())) => Some(Err(JsError::new(
+                            "Error",
+                            "promise can never settle, as the event loop is idle",
+                        ))),
+                    },
+                    v8::PromiseState::Fulfilled => {
+                        let value = promise.result(scope);
+                        Some(Ok(Value::from_v8(scope, value)))
+                    }
+                    v8::PromiseState::Rejected => {
+                        let exception = promise.result(scope);
+                        Some(Err(JsError::from_v8(scope, exception)))
+                    }
+                }
+            });
+            match result {
+                Some(result) => Poll::Ready(result),
+                None => Poll::Pending,
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let promise = &this.promise;
+            let future = this.future.get_or_insert_with(|| {
+                wasm_bindgen_futures::JsFuture::from(promise.promise.clone())
+            });
+            Pin::new(future).poll(cx).map(|result| {
+                let (state, result) = match result {
+                    Ok(value) => {
+                        let value = Value::from_web(value);
+                        (PromiseState::Fulfilled(value.clone()), Ok(value))
+                    }
+                    Err(error) => (
+                        PromiseState::Rejected(Value::from_web(error.clone())),
+                        Err(JsError::from_web(error)),
+                    ),
+                };
+                *promise.state.borrow_mut() = state;
+                result
+            })
+        }
+    }
+}
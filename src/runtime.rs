@@ -0,0 +1,437 @@
+use crate::{commonjs, JsError, LoadOptions, Module};
+
+/// Controls how a [`Runtime`] is created. See [`Runtime::with_options`] and
+/// [`Runtime::from_snapshot`].
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeOptions {
+    /// The heap's initial and maximum size in bytes. Scripts that reach the
+    /// maximum fail with [`JsError::OutOfMemory`] instead of aborting the
+    /// process. On the web, the heap can't be limited.
+    pub heap_limits: Option<(usize, usize)>,
+    /// See [`Runtime::set_timeout`].
+    pub timeout: Option<std::time::Duration>,
+}
+
+impl Runtime {
+    /// Runs a CommonJS script in a new context. The script receives `module`,
+    /// `exports`, `require`, `__filename` and `__dirname`, and the module's
+    /// exports are whatever `module.exports` holds once it finishes. `require`
+    /// resolves through the runtime's [`ModuleLoader`](crate::ModuleLoader).
+    pub fn load(&mut self, js: &str) -> Result<Module, JsError> {
+        self.load_with_options(js, &LoadOptions::default())
+    }
+
+    /// Like [`Runtime::load`], with `name` as the script's file name.
+    pub fn load_named(&mut self, name: &str, js: &str) -> Result<Module, JsError> {
+        self.load_with_options(js, &LoadOptions::new(name))
+    }
+
+    /// Like [`Runtime::load`], with the script's origin set by `options`.
+    pub fn load_with_options(&mut self, js: &str, options: &LoadOptions) -> Result<Module, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(source_map) = &options.source_map {
+            crate::source_map::register(
+                &mut self.isolate,
+                &options.name,
+                source_map.clone(),
+                options.line_offset,
+                options.column_offset,
+            );
+        }
+        let context = self.context();
+        let loader = self.loader.clone();
+        let exports = {
+            let mut scope = self.scope(&context);
+            commonjs::load(&mut scope, loader, js, options)?
+        };
+        Ok(Module { context, exports })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{rc::Rc, sync::Arc, task::Poll, time::Duration};
+
+    use crate::{
+        console::{self, Console},
+        es_module,
+        event_loop::{self, EventLoop, ThreadWaker},
+        intrinsics,
+        source_map::SourceMaps,
+        task::HostTasks,
+        termination, ConsoleSink, JsError, Module, ModuleLoader, RuntimeOptions, Scope, Snapshot,
+        TerminationHandle, Value,
+    };
+
+    /// Owns a JavaScript engine instance. Any number of modules can be loaded
+    /// into a runtime, and values can be freely shared between them.
+    pub struct Runtime {
+        pub(crate) isolate: v8::OwnedIsolate,
+        pub(crate) loader: Option<Rc<dyn ModuleLoader>>,
+        /// Entered while running the event loop, which isn't tied to a module.
+        main_context: v8::Global<v8::Context>,
+        timeout: Option<Duration>,
+    }
+
+    impl Runtime {
+        pub fn new() -> Self {
+            Self::with_options(&RuntimeOptions::default())
+        }
+
+        pub fn with_options(options: &RuntimeOptions) -> Self {
+            Self::with_params(v8::CreateParams::default(), options)
+        }
+
+        /// Creates a runtime whose heap starts at `initial` bytes and may grow to
+        /// `max` bytes. Scripts that reach the limit fail with
+        /// [`JsError::OutOfMemory`] instead of aborting the process.
+        pub fn with_heap_limits(initial: usize, max: usize) -> Self {
+            Self::with_options(&RuntimeOptions {
+                heap_limits: Some((initial, max)),
+                ..Default::default()
+            })
+        }
+
+        /// Creates a runtime whose contexts start from the state captured in
+        /// `snapshot`. Fails if the snapshot is corrupt or was created by a
+        /// different build of the engine.
+        pub fn from_snapshot(
+            snapshot: &Snapshot,
+            options: &RuntimeOptions,
+        ) -> Result<Self, JsError> {
+            let blob = snapshot.blob()?;
+            let params = v8::CreateParams::default().snapshot_blob(blob.to_vec());
+            Ok(Self::with_params(params, options))
+        }
+
+        fn with_params(mut params: v8::CreateParams, options: &RuntimeOptions) -> Self {
+            if let Some((initial, max)) = options.heap_limits {
+                params = params.heap_limits(initial, max);
+            }
+            let mut isolate = v8::Isolate::new(params);
+            if let Some((_, max)) = options.heap_limits {
+                termination::set_heap_limit(&mut isolate, max);
+            }
+            isolate.set_slot(HostTasks::default());
+            isolate.set_slot(EventLoop::default());
+            isolate.set_slot(SourceMaps::default());
+            isolate.set_slot(Console::default());
+            // microtasks run after every call into the runtime, and between timers
+            isolate.set_microtasks_policy(v8::MicrotasksPolicy::Explicit);
+            let main_context = new_context(&mut isolate);
+            // isolates are entered on creation, but we only enter them while in use
+            // so that several runtimes can live on the same thread
+            unsafe {
+                isolate.exit();
+            }
+            Self {
+                isolate,
+                loader: None,
+                main_context,
+                timeout: options.timeout,
+            }
+        }
+
+        /// Creates a new global environment.
+        pub fn context(&mut self) -> Context {
+            unsafe {
+                self.isolate.enter();
+            }
+            let context = new_context(&mut self.isolate);
+            unsafe {
+                self.isolate.exit();
+            }
+            Context(context)
+        }
+
+        pub fn scope<'a>(&'a mut self, context: &Context) -> Scope<'a, 'a> {
+            Scope::runtime(self, context.clone())
+        }
+
+        /// Sets the loader used to resolve ES module imports.
+        pub fn set_module_loader<L: ModuleLoader + 'static>(&mut self, loader: L) {
+            self.loader = Some(Rc::new(loader));
+        }
+
+        /// Imports an ES module, and everything it imports, into a new context.
+        /// The module's namespace is returned as its exports, once the module
+        /// has finished evaluating, including any top-level `await`.
+        pub async fn import(&mut self, specifier: &str) -> Result<Module, JsError> {
+            let loader = self
+                .loader
+                .clone()
+                .ok_or_else(|| JsError::new("Error", "no module loader is set"))?;
+            let context = self.context();
+            let (exports, evaluation) =
+                self.enter(&context.0, |scope| es_module::import(scope, loader, specifier))?;
+            // top-level await may wait on timers and host functions, so the
+            // module is only done once the event loop settles its evaluation
+            evaluation.into_future(&mut self.scope(&context)).await?;
+            Ok(Module {
+                context,
+                exports: Value::Object(exports),
+            })
+        }
+
+        /// Sets where messages written to `console` go. By default, they are
+        /// forwarded to `tracing`.
+        pub fn set_console_sink<S: ConsoleSink + 'static>(&mut self, sink: S) {
+            self.isolate.get_slot_mut::<Console>().unwrap().sink = Rc::new(sink);
+        }
+
+        /// Limits how long each call into the runtime may run for, including
+        /// loading scripts and each turn of the event loop. Scripts that run
+        /// past it fail with [`JsError::Terminated`].
+        pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+            self.timeout = timeout;
+        }
+
+        /// Returns a handle that can interrupt this runtime's scripts from
+        /// another thread.
+        pub fn termination_handle(&self) -> TerminationHandle {
+            TerminationHandle(self.isolate.thread_safe_handle())
+        }
+
+        /// Runs the event loop until no timers or async host functions are
+        /// pending, blocking the thread while waiting for them.
+        pub fn run_until_idle(&mut self) -> Result<(), JsError> {
+            let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+            let mut cx = std::task::Context::from_waker(&waker);
+            loop {
+                match self.poll(&mut cx) {
+                    Poll::Ready(result) => return result,
+                    Poll::Pending => std::thread::park(),
+                }
+            }
+        }
+
+        /// Runs the event loop until no timers or async host functions are
+        /// pending.
+        pub async fn run_event_loop(&mut self) -> Result<(), JsError> {
+            std::future::poll_fn(|cx| self.poll(cx)).await
+        }
+
+        /// Runs due timers, polls async host functions and performs a microtask
+        /// checkpoint. Returns `Ready` once no work is pending, or with the first
+        /// exception thrown by a timer.
+        pub fn poll(&mut self, cx: &mut std::task::Context) -> Poll<Result<(), JsError>> {
+            let context = self.main_context.clone();
+            self.enter(&context, |scope| event_loop::poll(scope, cx))
+        }
+
+        /// Whether any timers or async host functions are pending.
+        pub fn has_pending_work(&self) -> bool {
+            self.isolate
+                .get_slot::<EventLoop>()
+                .is_some_and(|event_loop| event_loop.has_pending_work())
+                || self
+                    .isolate
+                    .get_slot::<HostTasks>()
+                    .is_some_and(|host_tasks| host_tasks.has_pending_work())
+        }
+
+        pub(crate) fn enter<F, R>(&mut self, context: &v8::Global<v8::Context>, f: F) -> R
+        where
+            F: FnOnce(&mut v8::HandleScope<v8::Context>) -> R,
+        {
+            unsafe {
+                self.isolate.enter();
+            }
+            let result = {
+                let handle_scope = &mut v8::HandleScope::new(&mut self.isolate);
+                let context = v8::Local::new(handle_scope, context);
+                let scope: &mut v8::ContextScope<v8::HandleScope<_>> =
+                    &mut v8::ContextScope::new(handle_scope, context);
+                let previous = self
+                    .timeout
+                    .map(|timeout| termination::arm_watchdog(scope, timeout));
+                let result = f(scope);
+                if let Some(previous) = previous {
+                    termination::disarm_watchdog(scope, previous);
+                }
+                // the watchdog may fire just as `f` returns, leaving the
+                // termination pending for the next script
+                let terminated =
+                    termination::take_timed_out(scope) || scope.is_execution_terminating();
+                let out_of_memory = termination::heap_limit_exceeded(scope);
+                if terminated || out_of_memory {
+                    // the runtime stays usable once a terminated script has unwound
+                    scope.cancel_terminate_execution();
+                    termination::reset_heap_limit(scope);
+                } else {
+                    scope.perform_microtask_checkpoint();
+                }
+                result
+            };
+            unsafe {
+                self.isolate.exit();
+            }
+            result
+        }
+    }
+
+    impl Default for Runtime {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Drop for Runtime {
+        fn drop(&mut self) {
+            // the isolate must be entered to be disposed
+            unsafe {
+                self.isolate.enter();
+            }
+        }
+    }
+
+    /// A global environment within a [`Runtime`].
+    #[derive(Clone)]
+    pub struct Context(pub(crate) v8::Global<v8::Context>);
+
+    fn new_context(isolate: &mut v8::Isolate) -> v8::Global<v8::Context> {
+        let handle_scope = &mut v8::HandleScope::new(isolate);
+        let context = v8::Context::new(handle_scope);
+        let scope = &mut v8::ContextScope::new(handle_scope, context);
+        intrinsics::install(scope);
+        event_loop::install(scope);
+        console::install(scope);
+        v8::Global::new(scope, context)
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::*;
+
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use std::rc::Rc;
+
+    use crate::{
+        es_module, ConsoleSink, JsError, Module, ModuleLoader, RuntimeOptions, Scope, Snapshot,
+        TerminationHandle, Value,
+    };
+
+    /// Owns a JavaScript engine instance. Any number of modules can be loaded
+    /// into a runtime, and values can be freely shared between them.
+    ///
+    /// On the web, every runtime shares the page's engine.
+    pub struct Runtime {
+        pub(crate) loader: Option<Rc<dyn ModuleLoader>>,
+    }
+
+    impl Runtime {
+        pub fn new() -> Self {
+            Self { loader: None }
+        }
+
+        /// The page's heap can't be limited and its scripts can't be
+        /// interrupted, so this is the same as [`Runtime::new`].
+        #[allow(unused_variables)]
+        pub fn with_options(options: &RuntimeOptions) -> Self {
+            Self::new()
+        }
+
+        /// Creates a new global environment. On the web, every context shares
+        /// the page's global object.
+        pub fn context(&mut self) -> Context {
+            Context { _private: () }
+        }
+
+        #[allow(unused_variables)]
+        pub fn scope<'a>(&'a mut self, context: &Context) -> Scope<'a, 'a> {
+            Scope::new()
+        }
+
+        /// Sets the loader used to resolve ES module imports.
+        pub fn set_module_loader<L: ModuleLoader + 'static>(&mut self, loader: L) {
+            self.loader = Some(Rc::new(loader));
+        }
+
+        /// Imports an ES module, and everything it imports, into a new context.
+        /// The module's namespace is returned as its exports.
+        pub async fn import(&mut self, specifier: &str) -> Result<Module, JsError> {
+            let loader = self
+                .loader
+                .clone()
+                .ok_or_else(|| JsError::new("Error", "no module loader is set"))?;
+            let context = self.context();
+            let exports = es_module::import(loader, specifier).await?;
+            Ok(Module {
+                context,
+                exports: Value::Object(exports),
+            })
+        }
+
+        /// Creates a runtime from a snapshot. On the web, every context shares
+        /// the page's global object, so the snapshot's scripts are run once, here.
+        pub fn from_snapshot(
+            snapshot: &Snapshot,
+            options: &RuntimeOptions,
+        ) -> Result<Self, JsError> {
+            let source = std::str::from_utf8(&snapshot.0)
+                .map_err(|_| JsError::new("Error", "snapshot is not valid"))?;
+            js_sys::eval(source).map_err(JsError::from_web)?;
+            Ok(Self::with_options(options))
+        }
+
+        /// The page's heap can't be limited, so this is the same as
+        /// [`Runtime::new`].
+        #[allow(unused_variables)]
+        pub fn with_heap_limits(initial: usize, max: usize) -> Self {
+            Self::new()
+        }
+
+        /// Scripts on the web write to the browser's console, so the sink is
+        /// ignored.
+        #[allow(unused_variables)]
+        pub fn set_console_sink<S: ConsoleSink + 'static>(&mut self, sink: S) {}
+
+        /// Scripts can't be interrupted on the web, so the timeout is ignored.
+        #[allow(unused_variables)]
+        pub fn set_timeout(&mut self, timeout: Option<std::time::Duration>) {}
+
+        /// Returns a handle that can interrupt this runtime's scripts from
+        /// another thread. On the web, the handle does nothing.
+        pub fn termination_handle(&self) -> TerminationHandle {
+            TerminationHandle { _private: () }
+        }
+
+        /// Timers and microtasks are run by the browser, so this returns
+        /// immediately.
+        pub fn run_until_idle(&mut self) -> Result<(), JsError> {
+            Ok(())
+        }
+
+        /// Timers and microtasks are run by the browser, so this returns
+        /// immediately.
+        pub async fn run_event_loop(&mut self) -> Result<(), JsError> {
+            Ok(())
+        }
+
+        /// Timers and microtasks are run by the browser, so this is always
+        /// `Ready`.
+        pub fn poll(&mut self, _cx: &mut std::task::Context) -> std::task::Poll<Result<(), JsError>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        /// The browser's event loop can't be inspected, so this is always
+        /// `false`.
+        pub fn has_pending_work(&self) -> bool {
+            false
+        }
+    }
+
+    impl Default for Runtime {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// A global environment within a [`Runtime`].
+    #[derive(Clone)]
+    pub struct Context {
+        _private: (),
+    }
+}
+#[cfg(target_arch = "wasm32")]
+pub use wasm::*;
@@ -0,0 +1,128 @@
+use crate::{JsError, Scope, Value};
+
+/// A classic script that is compiled once and can be run any number of times,
+/// in any context of the [`Runtime`](crate::Runtime) it was compiled in.
+pub struct Script {
+    #[cfg(not(target_arch = "wasm32"))]
+    script: v8::Global<v8::UnboundScript>,
+    #[cfg(target_arch = "wasm32")]
+    source: String,
+    cache_rejected: bool,
+}
+
+impl Script {
+    /// Compiles `source`. `name` is the file name shown in stack traces.
+    pub fn compile(scope: &mut Scope, name: &str, source: &str) -> Result<Self, JsError> {
+        Self::compile_inner(scope, name, source, None)
+    }
+
+    /// Compiles `source`, skipping parsing by consuming a code cache created by
+    /// [`Script::code_cache`]. A cache that doesn't match the source or the
+    /// engine is ignored, and the source is compiled as usual.
+    pub fn compile_with_cache(
+        scope: &mut Scope,
+        name: &str,
+        source: &str,
+        cache: &[u8],
+    ) -> Result<Self, JsError> {
+        Self::compile_inner(scope, name, source, Some(cache))
+    }
+
+    #[allow(unused_variables)]
+    fn compile_inner(
+        scope: &mut Scope,
+        name: &str,
+        source: &str,
+        cache: Option<&[u8]>,
+    ) -> Result<Self, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use v8::script_compiler::{CachedData, CompileOptions, NoCacheReason, Source};
+            scope.try_enter(|scope| {
+                let code = v8::String::new(scope, source)?;
+                let origin = crate::module::script_origin(scope, name, 0, 0, false);
+                let (mut source, options) = match cache {
+                    Some(cache) => (
+                        Source::new_with_cached_data(code, Some(&origin), CachedData::new(cache)),
+                        CompileOptions::ConsumeCodeCache,
+                    ),
+                    None => (
+                        Source::new(code, Some(&origin)),
+                        CompileOptions::NoCompileOptions,
+                    ),
+                };
+                let script = v8::script_compiler::compile_unbound_script(
+                    scope,
+                    &mut source,
+                    options,
+                    NoCacheReason::NoReason,
+                )?;
+                // v8 compiles from source when it rejects a cache
+                let cache_rejected = source
+                    .get_cached_data()
+                    .is_some_and(|cache| cache.rejected());
+                Some(Self {
+                    script: v8::Global::new(scope, script),
+                    cache_rejected,
+                })
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(Self {
+                source: format!("{}\n//# sourceURL={}", source, name),
+                cache_rejected: cache.is_some(),
+            })
+        }
+    }
+
+    /// Runs the script in the scope's context, returning its completion value.
+    #[allow(unused_variables)]
+    pub fn run(&self, scope: &mut Scope) -> Result<Value, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.try_enter(|scope| {
+                let script = v8::Local::new(scope, &self.script);
+                let script = script.bind_to_current_context(scope);
+                let value = script.run(scope)?;
+                Some(Value::from_v8(scope, value))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::eval(&self.source)
+                .map(Value::from_web)
+                .map_err(JsError::from_web)
+        }
+    }
+
+    /// Creates a code cache that lets the script be compiled faster, even after
+    /// a restart, with [`Script::compile_with_cache`]. Returns `None` on the web,
+    /// where the browser manages its own cache.
+    #[allow(unused_variables)]
+    pub fn code_cache(&self, scope: &mut Scope) -> Option<Vec<u8>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let script = v8::Local::new(scope, &self.script);
+                v8::script_compiler::create_code_cache(script).map(|cache| cache.to_vec())
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            None
+        }
+    }
+
+    /// Whether the code cache passed to [`Script::compile_with_cache`] was
+    /// rejected, so the source had to be compiled from scratch.
+    pub fn cache_rejected(&self) -> bool {
+        self.cache_rejected
+    }
+}
+
+impl std::fmt::Debug for Script {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[script]")
+    }
+}
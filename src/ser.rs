@@ -0,0 +1,635 @@
+use std::cell::RefCell;
+
+use serde::ser::{self, Serialize};
+
+use crate::{value::MAX_SAFE_INTEGER, BigInt, Function, SerdeError, SerializeOptions, Value};
+
+/// A value in the engine, which serde converts without going through [`Value`].
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type RawValue<'s> = v8::Local<'s, v8::Value>;
+#[cfg(target_arch = "wasm32")]
+pub(crate) type RawValue<'s> = wasm_bindgen::JsValue;
+
+/// What's needed to create and inspect [`RawValue`]s. Browsers don't need
+/// anything.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type RawScope<'s> = v8::HandleScope<'s>;
+#[cfg(target_arch = "wasm32")]
+pub(crate) type RawScope<'s> = ();
+
+/// The newtype struct name that lets a [`Value`] pass through serde as is,
+/// when serialized or deserialized by unijs.
+pub(crate) const VALUE_TOKEN: &str = "$unijs::Value";
+
+thread_local! {
+    /// Holds the [`Value`] being passed through serde, since serde can only
+    /// pass Rust data.
+    pub(crate) static VALUE_SLOT: RefCell<Option<Value>> = const { RefCell::new(None) };
+}
+
+/// Builds engine values directly from Rust data.
+pub(crate) struct Serializer<'a, 's> {
+    scope: &'a mut RawScope<'s>,
+    options: &'a SerializeOptions,
+}
+
+impl<'a, 's> Serializer<'a, 's> {
+    pub(crate) fn new(scope: &'a mut RawScope<'s>, options: &'a SerializeOptions) -> Self {
+        Self { scope, options }
+    }
+
+    fn reborrow(&mut self) -> Serializer<'_, 's> {
+        Serializer {
+            scope: &mut *self.scope,
+            options: self.options,
+        }
+    }
+
+    fn integer(self, value: i128) -> Result<RawValue<'s>, SerdeError> {
+        let unsafe_integer = value.unsigned_abs() > MAX_SAFE_INTEGER as u128;
+        if self.options.large_integers_as_bigint && unsafe_integer {
+            Ok(raw_bigint(self.scope, &BigInt::from(value)))
+        } else {
+            Ok(raw_number(self.scope, value as f64))
+        }
+    }
+
+    fn array(self, items: Vec<RawValue<'s>>) -> SerializeArray<'a, 's> {
+        SerializeArray {
+            serializer: self,
+            items,
+            variant: None,
+        }
+    }
+
+    fn object(self, variant: Option<&'static str>) -> SerializeObject<'a, 's> {
+        SerializeObject {
+            serializer: self,
+            entries: vec![],
+            key: None,
+            variant,
+        }
+    }
+}
+
+impl<'a, 's> ser::Serializer for Serializer<'a, 's> {
+    type Ok = RawValue<'s>;
+    type Error = SerdeError;
+    type SerializeSeq = SerializeArray<'a, 's>;
+    type SerializeTuple = SerializeArray<'a, 's>;
+    type SerializeTupleStruct = SerializeArray<'a, 's>;
+    type SerializeTupleVariant = SerializeArray<'a, 's>;
+    type SerializeMap = SerializeObject<'a, 's>;
+    type SerializeStruct = SerializeObject<'a, 's>;
+    type SerializeStructVariant = SerializeObject<'a, 's>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(raw_bool(self.scope, v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.integer(v as i128)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.integer(v as i128)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.integer(v as i128)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        self.integer(v as i128)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.integer(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.integer(v as i128)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.integer(v as i128)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.integer(v as i128)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        self.integer(v as i128)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        match i128::try_from(v) {
+            Ok(v) => self.integer(v),
+            Err(_) if self.options.large_integers_as_bigint => {
+                Ok(raw_bigint(self.scope, &BigInt::from(v)))
+            }
+            Err(_) => Ok(raw_number(self.scope, v as f64)),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(raw_number(self.scope, v as f64))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(raw_number(self.scope, v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        raw_string(self.scope, v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        raw_string(self.scope, v)
+    }
+
+    /// Bytes become an array of numbers, like in JSON.
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        let scope = self.scope;
+        let items = v.iter().map(|byte| raw_number(scope, *byte as f64)).collect();
+        Ok(raw_array(scope, items))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(raw_null(self.scope))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(raw_null(self.scope))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(raw_null(self.scope))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        raw_string(self.scope, variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        if name == VALUE_TOKEN {
+            let value = VALUE_SLOT
+                .with(|slot| slot.borrow_mut().take())
+                .ok_or_else(|| SerdeError::Invalid("missing value".to_owned()))?;
+            raw_from_value(self.scope, &value)
+        } else {
+            value.serialize(self)
+        }
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        mut self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        let key = raw_string(self.scope, variant)?;
+        let value = value.serialize(self.reborrow())?;
+        raw_object(self.scope, vec![(key, value)])
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self.array(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self.array(Vec::with_capacity(len)))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self.array(Vec::with_capacity(len)))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        let mut array = self.array(Vec::with_capacity(len));
+        array.variant = Some(variant);
+        Ok(array)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self.object(None))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self.object(None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(self.object(Some(variant)))
+    }
+}
+
+pub(crate) struct SerializeArray<'a, 's> {
+    serializer: Serializer<'a, 's>,
+    items: Vec<RawValue<'s>>,
+    /// Wraps the array in `{ variant: [...] }`.
+    variant: Option<&'static str>,
+}
+
+impl<'a, 's> SerializeArray<'a, 's> {
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let value = value.serialize(self.serializer.reborrow())?;
+        self.items.push(value);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<RawValue<'s>, SerdeError> {
+        let scope = self.serializer.scope;
+        let array = raw_array(scope, self.items);
+        match self.variant {
+            Some(variant) => {
+                let key = raw_string(scope, variant)?;
+                raw_object(scope, vec![(key, array)])
+            }
+            None => Ok(array),
+        }
+    }
+}
+
+impl<'a, 's> ser::SerializeSeq for SerializeArray<'a, 's> {
+    type Ok = RawValue<'s>;
+    type Error = SerdeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, 's> ser::SerializeTuple for SerializeArray<'a, 's> {
+    type Ok = RawValue<'s>;
+    type Error = SerdeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, 's> ser::SerializeTupleStruct for SerializeArray<'a, 's> {
+    type Ok = RawValue<'s>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, 's> ser::SerializeTupleVariant for SerializeArray<'a, 's> {
+    type Ok = RawValue<'s>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+pub(crate) struct SerializeObject<'a, 's> {
+    serializer: Serializer<'a, 's>,
+    entries: Vec<(RawValue<'s>, RawValue<'s>)>,
+    key: Option<RawValue<'s>>,
+    /// Wraps the object in `{ variant: {...} }`.
+    variant: Option<&'static str>,
+}
+
+impl<'a, 's> SerializeObject<'a, 's> {
+    fn field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        let key = raw_string(self.serializer.scope, key)?;
+        let value = value.serialize(self.serializer.reborrow())?;
+        self.entries.push((key, value));
+        Ok(())
+    }
+
+    fn finish(self) -> Result<RawValue<'s>, SerdeError> {
+        let scope = self.serializer.scope;
+        let object = raw_object(scope, self.entries)?;
+        match self.variant {
+            Some(variant) => {
+                let key = raw_string(scope, variant)?;
+                raw_object(scope, vec![(key, object)])
+            }
+            None => Ok(object),
+        }
+    }
+}
+
+impl<'a, 's> ser::SerializeMap for SerializeObject<'a, 's> {
+    type Ok = RawValue<'s>;
+    type Error = SerdeError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.key = Some(key.serialize(self.serializer.reborrow())?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| SerdeError::Invalid("map value without a key".to_owned()))?;
+        let value = value.serialize(self.serializer.reborrow())?;
+        self.entries.push((key, value));
+        Ok(())
+    }
+
+    /// Maps become objects, unless their keys aren't all strings and
+    /// [`SerializeOptions::non_string_keys_as_map`] is set.
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        let scope = self.serializer.scope;
+        let string_keys = self.entries.iter().all(|(key, _)| key.is_string());
+        if !string_keys && self.serializer.options.non_string_keys_as_map {
+            return raw_map(scope, self.entries);
+        }
+        // objects stringify numbers and the like, but not objects
+        if self.entries.iter().any(|(key, _)| key.is_object()) {
+            return Err(SerdeError::Invalid(
+                "map keys must be strings or numbers".to_owned(),
+            ));
+        }
+        raw_object(scope, self.entries)
+    }
+}
+
+impl<'a, 's> ser::SerializeStruct for SerializeObject<'a, 's> {
+    type Ok = RawValue<'s>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, 's> ser::SerializeStructVariant for SerializeObject<'a, 's> {
+    type Ok = RawValue<'s>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+/// Passes the value through as is when serialized by unijs, for example by
+/// [`Value::serialize`]. Other serializers fail.
+impl Serialize for Value {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VALUE_SLOT.with(|slot| *slot.borrow_mut() = Some(self.clone()));
+        serializer.serialize_newtype_struct(VALUE_TOKEN, &ValueToken)
+    }
+}
+
+impl Serialize for Function {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Value::Function(self.clone()).serialize(serializer)
+    }
+}
+
+/// Only serialized by serializers that don't know about [`VALUE_TOKEN`].
+struct ValueToken;
+
+impl Serialize for ValueToken {
+    fn serialize<S: ser::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+        VALUE_SLOT.with(|slot| slot.borrow_mut().take());
+        Err(ser::Error::custom(
+            "javascript values can only be serialized by unijs",
+        ))
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_null<'s>(scope: &mut RawScope<'s>) -> RawValue<'s> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        v8::null(scope).into()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        wasm_bindgen::JsValue::null()
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_bool<'s>(scope: &mut RawScope<'s>, value: bool) -> RawValue<'s> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        v8::Boolean::new(scope, value).into()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        wasm_bindgen::JsValue::from_bool(value)
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_number<'s>(scope: &mut RawScope<'s>, value: f64) -> RawValue<'s> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        v8::Number::new(scope, value).into()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        wasm_bindgen::JsValue::from_f64(value)
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_bigint<'s>(scope: &mut RawScope<'s>, value: &BigInt) -> RawValue<'s> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        value.to_v8(scope).into()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        value.to_web().into()
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_string<'s>(scope: &mut RawScope<'s>, value: &str) -> Result<RawValue<'s>, SerdeError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // the thrown RangeError is reported instead
+        crate::string::new_v8_string(scope, value)
+            .map(Into::into)
+            .ok_or_else(|| SerdeError::Invalid("string is too long".to_owned()))
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        Ok(wasm_bindgen::JsValue::from_str(value))
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_from_value<'s>(
+    scope: &mut RawScope<'s>,
+    value: &Value,
+) -> Result<RawValue<'s>, SerdeError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // the thrown RangeError is reported instead
+        value
+            .to_v8(scope)
+            .ok_or_else(|| SerdeError::Invalid("string is too long".to_owned()))
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        Ok(value.to_web())
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_array<'s>(scope: &mut RawScope<'s>, items: Vec<RawValue<'s>>) -> RawValue<'s> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        v8::Array::new_with_elements(scope, &items).into()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        items.into_iter().collect::<js_sys::Array>().into()
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_object<'s>(
+    scope: &mut RawScope<'s>,
+    entries: Vec<(RawValue<'s>, RawValue<'s>)>,
+) -> Result<RawValue<'s>, SerdeError> {
+    // properties are defined rather than assigned, so keys like `__proto__`
+    // become own properties instead of going through setters
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let object = v8::Object::new(scope);
+        for (key, value) in entries {
+            let key = match v8::Local::<v8::Name>::try_from(key) {
+                Ok(key) => key,
+                Err(_) => key
+                    .to_string(scope)
+                    .ok_or_else(|| SerdeError::Invalid("invalid property key".to_owned()))?
+                    .into(),
+            };
+            if object.create_data_property(scope, key, value) != Some(true) {
+                return Err(SerdeError::Invalid("failed to set a property".to_owned()));
+            }
+        }
+        Ok(object.into())
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let object = js_sys::Object::new();
+        for (key, value) in entries {
+            let descriptor = js_sys::Object::new();
+            for (name, field) in [
+                ("value", value),
+                ("writable", true.into()),
+                ("enumerable", true.into()),
+                ("configurable", true.into()),
+            ] {
+                js_sys::Reflect::set(&descriptor, &name.into(), &field)
+                    .map_err(|error| SerdeError::Exception(crate::JsError::from_web(error)))?;
+            }
+            let defined = js_sys::Reflect::define_property(&object, &key, &descriptor)
+                .map_err(|error| SerdeError::Exception(crate::JsError::from_web(error)))?;
+            if !defined {
+                return Err(SerdeError::Invalid("failed to set a property".to_owned()));
+            }
+        }
+        Ok(object.into())
+    }
+}
+
+#[allow(unused_variables)]
+fn raw_map<'s>(
+    scope: &mut RawScope<'s>,
+    entries: Vec<(RawValue<'s>, RawValue<'s>)>,
+) -> Result<RawValue<'s>, SerdeError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let map = v8::Map::new(scope);
+        for (key, value) in entries {
+            map.set(scope, key, value)
+                .ok_or_else(|| SerdeError::Invalid("failed to set a map entry".to_owned()))?;
+        }
+        Ok(map.into())
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let map = js_sys::Map::new();
+        for (key, value) in entries {
+            map.set(&key, &value);
+        }
+        Ok(map.into())
+    }
+}
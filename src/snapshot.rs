@@ -0,0 +1,153 @@
+use crate::JsError;
+
+/// The serialized state of a runtime after running a set of scripts. Runtimes
+/// created from a snapshot start with every global those scripts defined.
+#[derive(Clone)]
+pub struct Snapshot(pub(crate) Vec<u8>);
+
+impl Snapshot {
+    /// Restores a snapshot from bytes previously returned by
+    /// [`Snapshot::as_bytes`]. Snapshots can only be loaded by the same build of
+    /// the engine that created them.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Starts every native snapshot, followed by the engine version, a checksum of
+/// the engine's blob and the blob itself. The engine crashes on a blob it
+/// can't read, so snapshots are checked before they are used.
+#[cfg(not(target_arch = "wasm32"))]
+const MAGIC: &[u8] = b"unijs-snapshot\0";
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Snapshot {
+    fn wrap(blob: &[u8]) -> Self {
+        let version = v8::V8::get_version().as_bytes();
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 1 + version.len() + 8 + blob.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(version.len() as u8);
+        bytes.extend_from_slice(version);
+        bytes.extend_from_slice(&checksum(blob).to_le_bytes());
+        bytes.extend_from_slice(blob);
+        Self(bytes)
+    }
+
+    /// The engine's blob, if the snapshot is intact and was created by this
+    /// build of the engine.
+    pub(crate) fn blob(&self) -> Result<&[u8], JsError> {
+        let invalid = || JsError::new("Error", "snapshot is not valid");
+        let bytes = self.0.strip_prefix(MAGIC).ok_or_else(invalid)?;
+        let (&version_len, bytes) = bytes.split_first().ok_or_else(invalid)?;
+        let version = bytes.get(..version_len as usize).ok_or_else(invalid)?;
+        if version != v8::V8::get_version().as_bytes() {
+            return Err(JsError::new(
+                "Error",
+                &format!(
+                    "snapshot was created by engine version {}, not {}",
+                    String::from_utf8_lossy(version),
+                    v8::V8::get_version()
+                ),
+            ));
+        }
+        let bytes = &bytes[version_len as usize..];
+        let checksum_bytes = bytes.get(..8).ok_or_else(invalid)?;
+        let blob = &bytes[8..];
+        if checksum_bytes != checksum(blob).to_le_bytes() {
+            return Err(invalid());
+        }
+        Ok(blob)
+    }
+}
+
+/// FNV-1a, to catch truncated or corrupted snapshots.
+#[cfg(not(target_arch = "wasm32"))]
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl std::fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[snapshot]")
+    }
+}
+
+/// Runs classic scripts, in order, into a [`Snapshot`].
+///
+/// Timers and `console` aren't installed while building, and functions created
+/// with [`Function::new`](crate::Function::new) can't be snapshotted, so scripts
+/// may only use them once a runtime has been created.
+#[derive(Default, Clone)]
+pub struct SnapshotBuilder {
+    scripts: Vec<(String, String)>,
+}
+
+impl SnapshotBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: &str, source: &str) {
+        self.scripts.push((name.to_owned(), source.to_owned()));
+    }
+
+    pub fn with(mut self, name: &str, source: &str) -> Self {
+        self.add(name, source);
+        self
+    }
+
+    /// Runs the scripts and serializes the result. On the web, the engine can't
+    /// be snapshotted, so the scripts are instead run by every runtime created
+    /// from the snapshot.
+    pub fn build(self) -> Result<Snapshot, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut isolate = v8::Isolate::snapshot_creator(None);
+            let result = {
+                let handle_scope = &mut v8::HandleScope::new(&mut isolate);
+                let context = v8::Context::new(handle_scope);
+                handle_scope.set_default_context(context);
+                let scope = &mut v8::ContextScope::new(handle_scope, context);
+                self.scripts
+                    .iter()
+                    .try_for_each(|(name, source)| run(scope, name, source))
+            };
+            // a snapshot creator must always create its blob before being dropped
+            let blob = isolate.create_blob(v8::FunctionCodeHandling::Keep);
+            result?;
+            let blob = blob.ok_or_else(|| JsError::new("Error", "failed to create snapshot"))?;
+            Ok(Snapshot::wrap(&blob))
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let source = self
+                .scripts
+                .iter()
+                .map(|(_, source)| source.as_str())
+                .collect::<Vec<_>>()
+                .join("\n;\n");
+            Ok(Snapshot(source.into_bytes()))
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn run(scope: &mut v8::HandleScope, name: &str, source: &str) -> Result<(), JsError> {
+    let scope = &mut v8::TryCatch::new(scope);
+    let code = v8::String::new(scope, source).unwrap();
+    let origin = crate::module::script_origin(scope, name, 0, 0, false);
+    v8::Script::compile(scope, code, Some(&origin))
+        .and_then(|script| script.run(scope))
+        .map(|_| ())
+        .ok_or_else(|| JsError::from_try_catch(scope))
+}
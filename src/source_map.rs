@@ -0,0 +1,338 @@
+use crate::JsError;
+
+/// A parsed version 3 source map, used to point stack traces at the original
+/// source of a generated or bundled script.
+#[derive(Clone)]
+pub struct SourceMap {
+    sources: Vec<String>,
+    /// Segments of each generated line, sorted by generated column.
+    lines: Vec<Vec<Segment>>,
+}
+
+#[derive(Clone, Copy)]
+struct Segment {
+    column: u32,
+    source: u32,
+    line: u32,
+    source_column: u32,
+}
+
+impl SourceMap {
+    /// Parses the JSON form of a source map.
+    pub fn parse(json: &str) -> Result<Self, JsError> {
+        let invalid = |message: &str| {
+            JsError::new("SyntaxError", &format!("invalid source map: {}", message))
+        };
+        let map: serde_json::Value =
+            serde_json::from_str(json).map_err(|err| invalid(&err.to_string()))?;
+        let source_root = map
+            .get("sourceRoot")
+            .and_then(|root| root.as_str())
+            .filter(|root| !root.is_empty());
+        let sources = map
+            .get("sources")
+            .and_then(|sources| sources.as_array())
+            .ok_or_else(|| invalid("missing sources"))?
+            .iter()
+            .map(|source| resolve_path(source_root, source.as_str().unwrap_or_default()))
+            .collect::<Vec<_>>();
+        let mappings = map
+            .get("mappings")
+            .and_then(|mappings| mappings.as_str())
+            .ok_or_else(|| invalid("missing mappings"))?;
+
+        // every field but the generated column is relative to the previous
+        // segment, even across lines
+        let mut lines = vec![];
+        let (mut source, mut line, mut source_column) = (0i64, 0i64, 0i64);
+        for mapping_line in mappings.split(';') {
+            let mut segments = vec![];
+            let mut column = 0i64;
+            for segment in mapping_line.split(',').filter(|segment| !segment.is_empty()) {
+                let fields = decode_vlq(segment).ok_or_else(|| invalid("bad mapping"))?;
+                column += fields[0];
+                // segments without a source don't map anywhere
+                if fields.len() < 4 {
+                    continue;
+                }
+                source += fields[1];
+                line += fields[2];
+                source_column += fields[3];
+                if column < 0 || source < 0 || line < 0 || source_column < 0 {
+                    return Err(invalid("negative position"));
+                }
+                segments.push(Segment {
+                    column: column as u32,
+                    source: source as u32,
+                    line: line as u32,
+                    source_column: source_column as u32,
+                });
+            }
+            segments.sort_by_key(|segment| segment.column);
+            lines.push(segments);
+        }
+        Ok(Self { sources, lines })
+    }
+
+    /// Finds the original location of a zero-based generated line and column,
+    /// returning the source's name and the zero-based line and column in it.
+    pub fn lookup(&self, line: u32, column: u32) -> Option<(&str, u32, u32)> {
+        let segments = self.lines.get(line as usize)?;
+        let index = segments
+            .partition_point(|segment| segment.column <= column)
+            .checked_sub(1)?;
+        let segment = segments[index];
+        let source = self.sources.get(segment.source as usize)?;
+        Some((source, segment.line, segment.source_column))
+    }
+}
+
+impl std::fmt::Debug for SourceMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[source map]")
+    }
+}
+
+/// Joins a source's path to the map's `sourceRoot`.
+fn resolve_path(root: Option<&str>, source: &str) -> String {
+    match root {
+        Some(root) => format!("{}/{}", root.trim_end_matches('/'), source),
+        None => source.to_owned(),
+    }
+}
+
+fn decode_vlq(segment: &str) -> Option<Vec<i64>> {
+    let mut fields = vec![];
+    let (mut value, mut shift) = (0i64, 0u32);
+    for byte in segment.bytes() {
+        let digit = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        } as i64;
+        if shift > 60 {
+            return None;
+        }
+        value += (digit & 31) << shift;
+        if digit & 32 != 0 {
+            shift += 5;
+        } else {
+            // the lowest bit is the sign
+            fields.push(if value & 1 != 0 { -(value >> 1) } else { value >> 1 });
+            value = 0;
+            shift = 0;
+        }
+    }
+    (shift == 0 && !fields.is_empty()).then_some(fields)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::collections::HashMap;
+
+    use super::SourceMap;
+
+    /// Source maps of the scripts loaded into a runtime, keyed by script name.
+    /// Stored in an isolate slot.
+    #[derive(Default)]
+    pub(crate) struct SourceMaps {
+        maps: HashMap<String, (SourceMap, i32, i32)>,
+    }
+
+    pub(crate) fn register(
+        isolate: &mut v8::Isolate,
+        name: &str,
+        map: SourceMap,
+        line_offset: i32,
+        column_offset: i32,
+    ) {
+        if let Some(source_maps) = isolate.get_slot_mut::<SourceMaps>() {
+            source_maps
+                .maps
+                .insert(name.to_owned(), (map, line_offset, column_offset));
+        }
+    }
+
+    /// Rewrites the frames of a stack trace that point into scripts with a
+    /// source map, so that they point at the original source instead.
+    pub(crate) fn remap_stack(isolate: &v8::Isolate, stack: String) -> String {
+        let Some(source_maps) = isolate.get_slot::<SourceMaps>() else {
+            return stack;
+        };
+        if source_maps.maps.is_empty() {
+            return stack;
+        }
+        stack
+            .lines()
+            .map(|frame| remap_frame(source_maps, frame).unwrap_or_else(|| frame.to_owned()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Remaps a frame such as `    at add (math.js:3:9)` or `    at math.js:3:9`.
+    fn remap_frame(source_maps: &SourceMaps, frame: &str) -> Option<String> {
+        let location = frame.trim_end_matches(')');
+        let (rest, column) = location.rsplit_once(':')?;
+        let (rest, line) = rest.rsplit_once(':')?;
+        let column = column.parse::<i32>().ok()?;
+        let line = line.parse::<i32>().ok()?;
+        let start = match rest.rfind('(') {
+            Some(index) => index + 1,
+            None => rest.find("at ")? + 3,
+        };
+        let (map, line_offset, column_offset) = source_maps.maps.get(&rest[start..])?;
+
+        // frames are one-based and include the script's offsets
+        let line = line - 1 - line_offset;
+        let column = column - 1 - if line == 0 { *column_offset } else { 0 };
+        let (source, line, column) = map.lookup(line.try_into().ok()?, column.try_into().ok()?)?;
+        Some(format!(
+            "{}{}:{}:{}{}",
+            &frame[..start],
+            source,
+            line + 1,
+            column + 1,
+            &frame[location.len()..]
+        ))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{remap_frame, SourceMap, SourceMaps};
+        use crate::source_map::tests::JSON;
+
+        fn source_maps(line_offset: i32, column_offset: i32) -> SourceMaps {
+            let mut source_maps = SourceMaps::default();
+            let map = SourceMap::parse(JSON).unwrap();
+            source_maps
+                .maps
+                .insert("bundle.js".to_owned(), (map, line_offset, column_offset));
+            source_maps
+        }
+
+        #[test]
+        fn remaps_named_and_anonymous_frames() {
+            let source_maps = source_maps(0, 0);
+            assert_eq!(
+                remap_frame(&source_maps, "    at add (bundle.js:1:5)").as_deref(),
+                Some("    at add (src/a.ts:1:5)")
+            );
+            assert_eq!(
+                remap_frame(&source_maps, "    at bundle.js:3:6").as_deref(),
+                Some("    at src/b.ts:1:2")
+            );
+        }
+
+        #[test]
+        fn applies_script_offsets() {
+            let source_maps = source_maps(2, 10);
+            // the column offset only applies to the script's first line
+            assert_eq!(
+                remap_frame(&source_maps, "    at add (bundle.js:3:11)").as_deref(),
+                Some("    at add (src/a.ts:1:1)")
+            );
+            assert_eq!(
+                remap_frame(&source_maps, "    at add (bundle.js:4:2)").as_deref(),
+                Some("    at add (src/a.ts:2:1)")
+            );
+            assert_eq!(remap_frame(&source_maps, "    at add (bundle.js:1:5)"), None);
+        }
+
+        #[test]
+        fn leaves_other_frames_alone() {
+            let source_maps = source_maps(0, 0);
+            assert_eq!(remap_frame(&source_maps, "    at add (other.js:1:5)"), None);
+            assert_eq!(remap_frame(&source_maps, "    at <anonymous>"), None);
+            assert_eq!(remap_frame(&source_maps, "Error: failed"), None);
+        }
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_vlq, resolve_path, SourceMap};
+
+    // line 0 maps a.ts 0:0 and 0:4, line 1 a.ts 1:0 and 1:2 plus a segment
+    // without a source, and line 2 steps back to b.ts 0:0 and 0:1
+    pub(crate) const JSON: &str = r#"{
+        "version": 3,
+        "sourceRoot": "src/",
+        "sources": ["a.ts", "b.ts"],
+        "names": [],
+        "mappings": "AAAA,IAAI;AACJ,EAAE,K;ACDF,GAAC"
+    }"#;
+
+    #[test]
+    fn decodes_vlq() {
+        assert_eq!(decode_vlq("AAAA"), Some(vec![0, 0, 0, 0]));
+        assert_eq!(decode_vlq("IAAI"), Some(vec![4, 0, 0, 4]));
+        assert_eq!(decode_vlq("D"), Some(vec![-1]));
+        assert_eq!(decode_vlq("gB"), Some(vec![16]));
+        assert_eq!(decode_vlq("hB"), Some(vec![-16]));
+        assert_eq!(decode_vlq("2HwBA"), Some(vec![123, 24, 0]));
+    }
+
+    #[test]
+    fn rejects_bad_vlq() {
+        assert_eq!(decode_vlq(""), None);
+        assert_eq!(decode_vlq("!"), None);
+        // a continuation bit without a following digit
+        assert_eq!(decode_vlq("g"), None);
+        assert_eq!(decode_vlq("ggggggggggggggB"), None);
+    }
+
+    #[test]
+    fn looks_up_segments() {
+        let map = SourceMap::parse(JSON).unwrap();
+        assert_eq!(map.lookup(0, 0), Some(("src/a.ts", 0, 0)));
+        assert_eq!(map.lookup(0, 3), Some(("src/a.ts", 0, 0)));
+        assert_eq!(map.lookup(0, 4), Some(("src/a.ts", 0, 4)));
+        assert_eq!(map.lookup(0, 100), Some(("src/a.ts", 0, 4)));
+        assert_eq!(map.lookup(1, 1), Some(("src/a.ts", 1, 0)));
+        // segments without a source are skipped
+        assert_eq!(map.lookup(1, 7), Some(("src/a.ts", 1, 2)));
+    }
+
+    #[test]
+    fn applies_negative_deltas() {
+        let map = SourceMap::parse(JSON).unwrap();
+        assert_eq!(map.lookup(2, 0), Some(("src/b.ts", 0, 0)));
+        assert_eq!(map.lookup(2, 5), Some(("src/b.ts", 0, 1)));
+    }
+
+    #[test]
+    fn misses_unmapped_positions() {
+        let json = r#"{"version": 3, "sources": ["a.ts"], "mappings": ";EAAA"}"#;
+        let map = SourceMap::parse(json).unwrap();
+        assert_eq!(map.lookup(0, 0), None);
+        assert_eq!(map.lookup(1, 1), None);
+        assert_eq!(map.lookup(1, 2), Some(("a.ts", 0, 0)));
+        assert_eq!(map.lookup(2, 0), None);
+    }
+
+    #[test]
+    fn rejects_invalid_maps() {
+        assert!(SourceMap::parse("{").is_err());
+        assert!(SourceMap::parse(r#"{"mappings": "AAAA"}"#).is_err());
+        assert!(SourceMap::parse(r#"{"sources": []}"#).is_err());
+        let negative = r#"{"sources": ["a.ts"], "mappings": "AAAD"}"#;
+        assert!(SourceMap::parse(negative).is_err());
+        let bad = r#"{"sources": ["a.ts"], "mappings": "A!AA"}"#;
+        assert!(SourceMap::parse(bad).is_err());
+    }
+
+    #[test]
+    fn joins_source_root() {
+        assert_eq!(resolve_path(Some("src/"), "a.ts"), "src/a.ts");
+        assert_eq!(resolve_path(Some("src"), "a.ts"), "src/a.ts");
+        assert_eq!(resolve_path(None, "a.ts"), "a.ts");
+        let json = r#"{"sourceRoot": "", "sources": ["a.ts"], "mappings": "AAAA"}"#;
+        let map = SourceMap::parse(json).unwrap();
+        assert_eq!(map.lookup(0, 0), Some(("a.ts", 0, 0)));
+    }
+}
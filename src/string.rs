@@ -0,0 +1,203 @@
+use std::ops::Deref;
+
+use crate::Value;
+
+/// A JavaScript string. JavaScript strings are UTF-16 and may contain lone
+/// surrogates, which UTF-8 can't represent. Such strings are readable as a
+/// `str` with the surrogates replaced by `U+FFFD`, and keep their original code
+/// units so they reach JavaScript unchanged.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct JsString {
+    string: String,
+    /// Only kept when the string isn't valid UTF-16.
+    utf16: Option<Vec<u16>>,
+}
+
+impl JsString {
+    pub fn new(string: impl Into<String>) -> Self {
+        Self {
+            string: string.into(),
+            utf16: None,
+        }
+    }
+
+    /// Creates a string from UTF-16 code units, keeping any lone surrogates.
+    pub fn from_utf16(utf16: Vec<u16>) -> Self {
+        match String::from_utf16(&utf16) {
+            Ok(string) => Self::new(string),
+            Err(_) => Self {
+                string: String::from_utf16_lossy(&utf16),
+                utf16: Some(utf16),
+            },
+        }
+    }
+
+    /// The string, with lone surrogates replaced by `U+FFFD`.
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    /// The string, with lone surrogates replaced by `U+FFFD`.
+    pub fn into_string(self) -> String {
+        self.string
+    }
+
+    /// The exact UTF-16 code units, including lone surrogates.
+    pub fn to_utf16(&self) -> Vec<u16> {
+        match &self.utf16 {
+            Some(utf16) => utf16.clone(),
+            None => self.string.encode_utf16().collect(),
+        }
+    }
+
+    /// The length in UTF-16 code units, like `String.prototype.length`.
+    pub fn len_utf16(&self) -> usize {
+        match &self.utf16 {
+            Some(utf16) => utf16.len(),
+            None => self.string.encode_utf16().count(),
+        }
+    }
+
+    /// Whether the string has no lone surrogates, so [`JsString::as_str`] is
+    /// lossless. Like `String.prototype.isWellFormed`.
+    pub fn is_well_formed(&self) -> bool {
+        self.utf16.is_none()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        string: v8::Local<'b, v8::String>,
+    ) -> Self {
+        // one byte strings are Latin-1, so they can't have surrogates
+        if string.is_onebyte() {
+            return Self::new(string.to_rust_string_lossy(scope));
+        }
+        let mut utf16 = vec![0; string.length()];
+        string.write(scope, &mut utf16, 0, v8::WriteOptions::NO_NULL_TERMINATION);
+        Self::from_utf16(utf16)
+    }
+
+    /// Returns `None`, with a `RangeError` thrown, if the string is longer
+    /// than the engine allows.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+    ) -> Option<v8::Local<'s, v8::String>> {
+        match &self.utf16 {
+            Some(utf16) => {
+                let string = v8::String::new_from_two_byte(scope, utf16, v8::NewStringType::Normal);
+                if string.is_none() {
+                    throw_invalid_length(scope);
+                }
+                string
+            }
+            None => new_v8_string(scope, &self.string),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(string: js_sys::JsString) -> Self {
+        if string.is_valid_utf16() {
+            Self::new(String::from(string))
+        } else {
+            Self::from_utf16(string.iter().collect())
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> js_sys::JsString {
+        match &self.utf16 {
+            Some(utf16) => js_sys::JsString::from_char_code(utf16),
+            None => js_sys::JsString::from(self.string.as_str()),
+        }
+    }
+}
+
+/// Creates an engine string, or returns `None` with a `RangeError` thrown, like
+/// JavaScript does, if `string` is longer than the engine allows.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn new_v8_string<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    string: &str,
+) -> Option<v8::Local<'s, v8::String>> {
+    let result = v8::String::new(scope, string);
+    if result.is_none() {
+        throw_invalid_length(scope);
+    }
+    result
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn throw_invalid_length(scope: &mut v8::HandleScope) {
+    let message = v8::String::new(scope, "Invalid string length").unwrap();
+    let exception = v8::Exception::range_error(scope, message);
+    scope.throw_exception(exception);
+}
+
+impl Deref for JsString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.string
+    }
+}
+
+impl std::fmt::Display for JsString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.string.fmt(f)
+    }
+}
+
+impl std::fmt::Debug for JsString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.string.fmt(f)
+    }
+}
+
+impl From<String> for JsString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for JsString {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Becomes a [`Value::String`], unless the string has lone surrogates.
+impl From<JsString> for Value {
+    fn from(value: JsString) -> Self {
+        match value.utf16 {
+            Some(_) => Value::IllFormedString(value),
+            None => Value::String(value.string),
+        }
+    }
+}
+
+impl From<JsString> for String {
+    fn from(value: JsString) -> Self {
+        value.into_string()
+    }
+}
+
+impl PartialEq<str> for JsString {
+    fn eq(&self, other: &str) -> bool {
+        self.is_well_formed() && self.string == other
+    }
+}
+
+impl PartialEq<&str> for JsString {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for JsString {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{Scope, Value};
+
+/// A JavaScript `Symbol`, a unique value mostly used as a property key.
+#[derive(Clone)]
+pub struct Symbol {
+    #[cfg(not(target_arch = "wasm32"))]
+    symbol: v8::Global<v8::Symbol>,
+    #[cfg(target_arch = "wasm32")]
+    symbol: js_sys::Symbol,
+}
+
+impl Symbol {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        symbol: v8::Local<'b, v8::Symbol>,
+    ) -> Self {
+        Self {
+            symbol: v8::Global::new(scope, symbol),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(&self, scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::Symbol> {
+        v8::Local::new(scope, &self.symbol)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(symbol: js_sys::Symbol) -> Self {
+        Self { symbol }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> js_sys::Symbol {
+        self.symbol.clone()
+    }
+
+    /// Creates a new unique symbol, like `Symbol(description)`.
+    #[allow(unused_variables)]
+    pub fn new(scope: &mut Scope, description: Option<&str>) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let symbol = scope.enter(|scope| {
+                let description = description.map(|description| {
+                    v8::String::new(scope, description).unwrap()
+                });
+                let symbol = v8::Symbol::new(scope, description);
+                v8::Global::new(scope, symbol)
+            });
+            Self { symbol }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use wasm_bindgen::{JsCast, JsValue};
+            // symbols can't be constructed, so call the global function
+            let constructor: js_sys::Function =
+                js_sys::Reflect::get(&js_sys::global(), &JsValue::from("Symbol"))
+                    .unwrap()
+                    .unchecked_into();
+            let description = description.map(JsValue::from).unwrap_or_default();
+            Self {
+                symbol: constructor
+                    .call1(&JsValue::undefined(), &description)
+                    .unwrap()
+                    .unchecked_into(),
+            }
+        }
+    }
+
+    /// Gets the symbol for `key` from the global registry, creating it if
+    /// needed, like `Symbol.for(key)`.
+    #[allow(unused_variables)]
+    pub fn for_key(scope: &mut Scope, key: &str) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let symbol = scope.enter(|scope| {
+                let key = v8::String::new(scope, key).unwrap();
+                let symbol = v8::Symbol::for_key(scope, key);
+                v8::Global::new(scope, symbol)
+            });
+            Self { symbol }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                symbol: js_sys::Symbol::for_(key),
+            }
+        }
+    }
+
+    /// `Symbol.iterator`
+    #[allow(unused_variables)]
+    pub fn iterator(scope: &mut Scope) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self::well_known(scope, v8::Symbol::get_iterator)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                symbol: js_sys::Symbol::iterator(),
+            }
+        }
+    }
+
+    /// `Symbol.asyncIterator`
+    #[allow(unused_variables)]
+    pub fn async_iterator(scope: &mut Scope) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self::well_known(scope, v8::Symbol::get_async_iterator)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                symbol: js_sys::Symbol::async_iterator(),
+            }
+        }
+    }
+
+    /// `Symbol.toStringTag`
+    #[allow(unused_variables)]
+    pub fn to_string_tag(scope: &mut Scope) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self::well_known(scope, v8::Symbol::get_to_string_tag)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                symbol: js_sys::Symbol::to_string_tag(),
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn well_known(
+        scope: &mut Scope,
+        get: for<'s> fn(&mut v8::HandleScope<'s, ()>) -> v8::Local<'s, v8::Symbol>,
+    ) -> Self {
+        let symbol = scope.enter(|scope| {
+            let symbol = get(scope);
+            v8::Global::new(scope, symbol)
+        });
+        Self { symbol }
+    }
+
+    /// The description the symbol was created with, if any.
+    #[allow(unused_variables)]
+    pub fn description(&self, scope: &mut Scope) -> Option<String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let symbol = self.symbol.clone();
+            scope.enter(move |scope| {
+                let symbol = v8::Local::new(scope, symbol);
+                let description = symbol.description(scope);
+                if description.is_undefined() {
+                    None
+                } else {
+                    Some(description.to_rust_string_lossy(scope))
+                }
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.symbol.description()
+        }
+    }
+}
+
+impl std::fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[symbol]")
+    }
+}
+
+impl From<Symbol> for Value {
+    fn from(value: Symbol) -> Self {
+        Value::Symbol(value)
+    }
+}
+
+/// The key of an object property, either a string or a symbol.
+#[derive(Debug, Clone)]
+pub enum PropertyKey {
+    String(String),
+    Symbol(Symbol),
+}
+
+impl PropertyKey {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn from_v8<'a, 'b>(
+        scope: &mut v8::HandleScope<'a>,
+        key: v8::Local<'b, v8::Value>,
+    ) -> Option<Self> {
+        if key.is_symbol() {
+            Some(Self::Symbol(Symbol::from_v8(scope, key.try_into().unwrap())))
+        } else if key.is_string() || key.is_number() {
+            Some(Self::String(key.to_rust_string_lossy(scope)))
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_v8<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+    ) -> Option<v8::Local<'s, v8::Value>> {
+        match self {
+            Self::String(key) => crate::string::new_v8_string(scope, key).map(Into::into),
+            Self::Symbol(key) => Some(key.to_v8(scope).into()),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn from_web(key: wasm_bindgen::JsValue) -> Option<Self> {
+        use wasm_bindgen::JsCast;
+        if key.is_symbol() {
+            Some(Self::Symbol(Symbol::from_web(key.unchecked_into())))
+        } else {
+            key.as_string().map(Self::String)
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn to_web(&self) -> wasm_bindgen::JsValue {
+        match self {
+            Self::String(key) => wasm_bindgen::JsValue::from(key),
+            Self::Symbol(key) => key.to_web().into(),
+        }
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, Self::String(..))
+    }
+
+    pub fn is_symbol(&self) -> bool {
+        matches!(self, Self::Symbol(..))
+    }
+
+    pub fn into_string(self) -> Option<String> {
+        if let Self::String(key) = self {
+            Some(key)
+        } else {
+            None
+        }
+    }
+
+    pub fn into_symbol(self) -> Option<Symbol> {
+        if let Self::Symbol(key) = self {
+            Some(key)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<&str> for PropertyKey {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for PropertyKey {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&String> for PropertyKey {
+    fn from(value: &String) -> Self {
+        Self::String(value.clone())
+    }
+}
+
+impl From<Symbol> for PropertyKey {
+    fn from(value: Symbol) -> Self {
+        Self::Symbol(value)
+    }
+}
+
+impl From<&Symbol> for PropertyKey {
+    fn from(value: &Symbol) -> Self {
+        Self::Symbol(value.clone())
+    }
+}
+
+impl From<PropertyKey> for Value {
+    fn from(value: PropertyKey) -> Self {
+        match value {
+            PropertyKey::String(key) => Value::String(key),
+            PropertyKey::Symbol(key) => Value::Symbol(key),
+        }
+    }
+}
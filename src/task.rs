@@ -0,0 +1,88 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use crate::Value;
+
+    type HostFuture = Pin<Box<dyn Future<Output = Result<Value, Value>>>>;
+
+    /// Futures started by async host functions, each settling a promise that was
+    /// handed to JavaScript. Stored in an isolate slot.
+    #[derive(Default)]
+    pub(crate) struct HostTasks {
+        tasks: Vec<(HostFuture, v8::Global<v8::PromiseResolver>)>,
+        unpolled: bool,
+    }
+
+    impl HostTasks {
+        pub(crate) fn has_pending_work(&self) -> bool {
+            !self.tasks.is_empty()
+        }
+
+        /// Whether tasks were spawned since the last poll, and so have not yet
+        /// registered a waker.
+        pub(crate) fn has_unpolled(&self) -> bool {
+            self.unpolled
+        }
+    }
+
+    /// Starts tracking `future`, returning a promise that settles with its result.
+    pub(crate) fn spawn<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        future: impl Future<Output = Result<Value, Value>> + 'static,
+    ) -> v8::Local<'s, v8::Promise> {
+        let resolver = v8::PromiseResolver::new(scope).unwrap();
+        let promise = resolver.get_promise(scope);
+        let resolver = v8::Global::new(scope, resolver);
+        let host_tasks = scope
+            .get_slot_mut::<HostTasks>()
+            .expect("isolate was not created by a runtime");
+        host_tasks.tasks.push((Box::pin(future), resolver));
+        host_tasks.unpolled = true;
+        promise
+    }
+
+    /// Polls every pending host future, settling the promises of those that have
+    /// finished. Returns whether any are still pending.
+    pub(crate) fn poll(scope: &mut v8::HandleScope, cx: &mut Context) -> bool {
+        let Some(host_tasks) = scope.get_slot_mut::<HostTasks>() else {
+            return false;
+        };
+        // taken out of the slot, since settling a promise can spawn more tasks
+        let tasks = std::mem::take(&mut host_tasks.tasks);
+        host_tasks.unpolled = false;
+        let mut pending = vec![];
+        for (mut future, resolver) in tasks {
+            match future.as_mut().poll(cx) {
+                Poll::Ready(result) => {
+                    let resolver = v8::Local::new(scope, resolver);
+                    // a string that's too long rejects the promise instead
+                    let scope = &mut v8::TryCatch::new(scope);
+                    let settled = match result {
+                        Ok(value) => value
+                            .to_v8(scope)
+                            .map(|value| resolver.resolve(scope, value)),
+                        Err(exception) => exception
+                            .to_v8(scope)
+                            .map(|exception| resolver.reject(scope, exception)),
+                    };
+                    if settled.is_none() {
+                        let exception = scope.exception().unwrap();
+                        resolver.reject(scope, exception);
+                    }
+                }
+                Poll::Pending => pending.push((future, resolver)),
+            }
+        }
+        let host_tasks = scope.get_slot_mut::<HostTasks>().unwrap();
+        pending.append(&mut host_tasks.tasks);
+        host_tasks.tasks = pending;
+        !host_tasks.tasks.is_empty()
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
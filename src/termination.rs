@@ -0,0 +1,192 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{
+        ffi::c_void,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Condvar, Mutex,
+        },
+        thread::JoinHandle,
+        time::{Duration, Instant},
+    };
+
+    /// Interrupts JavaScript running in a [`Runtime`](crate::Runtime) from any
+    /// thread. The interrupted call fails with [`JsError::Terminated`](crate::JsError::Terminated),
+    /// and the runtime can be used again afterwards.
+    #[derive(Clone)]
+    pub struct TerminationHandle(pub(crate) v8::IsolateHandle);
+
+    impl TerminationHandle {
+        /// Stops the script that is currently running. If no script is running,
+        /// the next one is stopped as soon as it starts. Returns `false` if the
+        /// runtime has been dropped.
+        pub fn terminate(&self) -> bool {
+            self.0.terminate_execution()
+        }
+    }
+
+    /// Terminates scripts that run past their deadline. Stored in an isolate
+    /// slot, and started the first time a timeout is used. Its thread lives as
+    /// long as the runtime, and calls only publish their deadline to it.
+    pub(crate) struct Watchdog {
+        shared: Arc<(Mutex<WatchdogState>, Condvar)>,
+        thread: Option<JoinHandle<()>>,
+    }
+
+    #[derive(Default)]
+    struct WatchdogState {
+        deadline: Option<Instant>,
+        /// Whether a script was terminated since the last call ended.
+        fired: bool,
+        stopped: bool,
+    }
+
+    impl Watchdog {
+        fn new(handle: v8::IsolateHandle) -> Self {
+            let shared = Arc::new((Mutex::new(WatchdogState::default()), Condvar::new()));
+            let thread = std::thread::spawn({
+                let shared = shared.clone();
+                move || {
+                    let (state, condvar) = &*shared;
+                    let mut state = state.lock().unwrap();
+                    while !state.stopped {
+                        let now = Instant::now();
+                        let deadline = state.deadline;
+                        state = match deadline {
+                            Some(deadline) if deadline <= now => {
+                                // terminating while holding the lock, so that the call
+                                // can't finish in between
+                                state.deadline = None;
+                                state.fired |= handle.terminate_execution();
+                                state
+                            }
+                            Some(deadline) => {
+                                condvar.wait_timeout(state, deadline - now).unwrap().0
+                            }
+                            None => condvar.wait(state).unwrap(),
+                        };
+                    }
+                }
+            });
+            Self {
+                shared,
+                thread: Some(thread),
+            }
+        }
+    }
+
+    impl Drop for Watchdog {
+        fn drop(&mut self) {
+            let (state, condvar) = &*self.shared;
+            state.lock().unwrap().stopped = true;
+            condvar.notify_one();
+            if let Some(thread) = self.thread.take() {
+                thread.join().ok();
+            }
+        }
+    }
+
+    /// Publishes a deadline `timeout` from now to the watchdog, unless an
+    /// enclosing call's deadline is sooner. Returns the deadline to restore with
+    /// [`disarm_watchdog`] once the call ends.
+    pub(crate) fn arm_watchdog(isolate: &mut v8::Isolate, timeout: Duration) -> Option<Instant> {
+        if isolate.get_slot::<Watchdog>().is_none() {
+            let watchdog = Watchdog::new(isolate.thread_safe_handle());
+            isolate.set_slot(watchdog);
+        }
+        let (state, condvar) = &*isolate.get_slot::<Watchdog>().unwrap().shared;
+        let mut state = state.lock().unwrap();
+        let previous = state.deadline;
+        let deadline = Instant::now() + timeout;
+        state.deadline = Some(previous.map_or(deadline, |previous| previous.min(deadline)));
+        condvar.notify_one();
+        previous
+    }
+
+    /// Restores the deadline that was in place before [`arm_watchdog`].
+    pub(crate) fn disarm_watchdog(isolate: &v8::Isolate, previous: Option<Instant>) {
+        if let Some(watchdog) = isolate.get_slot::<Watchdog>() {
+            let (state, _) = &*watchdog.shared;
+            state.lock().unwrap().deadline = previous;
+        }
+    }
+
+    /// Whether the watchdog terminated a script since this was last called.
+    pub(crate) fn take_timed_out(isolate: &v8::Isolate) -> bool {
+        isolate.get_slot::<Watchdog>().is_some_and(|watchdog| {
+            let (state, _) = &*watchdog.shared;
+            std::mem::take(&mut state.lock().unwrap().fired)
+        })
+    }
+
+    /// Terminates scripts that reach a runtime's heap limit. Stored in an
+    /// isolate slot, which keeps it alive for as long as the callback is
+    /// registered.
+    pub(crate) struct HeapLimit {
+        handle: v8::IsolateHandle,
+        max: usize,
+        exceeded: AtomicBool,
+    }
+
+    pub(crate) fn set_heap_limit(isolate: &mut v8::Isolate, max: usize) {
+        let heap_limit = Arc::new(HeapLimit {
+            handle: isolate.thread_safe_handle(),
+            max,
+            exceeded: AtomicBool::new(false),
+        });
+        let data = Arc::as_ptr(&heap_limit) as *mut c_void;
+        isolate.set_slot(heap_limit);
+        isolate.add_near_heap_limit_callback(near_heap_limit, data);
+    }
+
+    extern "C" fn near_heap_limit(data: *mut c_void, current_heap_limit: usize, _: usize) -> usize {
+        let heap_limit = unsafe { &*(data as *const HeapLimit) };
+        heap_limit.exceeded.store(true, Ordering::SeqCst);
+        heap_limit.handle.terminate_execution();
+        // v8 aborts the process if the heap stays full, so leave room for the
+        // script to unwind
+        current_heap_limit * 2
+    }
+
+    /// Whether the script being terminated has reached the heap limit.
+    pub(crate) fn heap_limit_exceeded(isolate: &v8::Isolate) -> bool {
+        isolate
+            .get_slot::<Arc<HeapLimit>>()
+            .is_some_and(|heap_limit| heap_limit.exceeded.load(Ordering::SeqCst))
+    }
+
+    /// Restores the heap limit after a script reached it and was terminated.
+    pub(crate) fn reset_heap_limit(isolate: &mut v8::Isolate) {
+        let Some(heap_limit) = isolate.get_slot::<Arc<HeapLimit>>().cloned() else {
+            return;
+        };
+        if heap_limit.exceeded.swap(false, Ordering::SeqCst) {
+            let data = Arc::as_ptr(&heap_limit) as *mut c_void;
+            isolate.remove_near_heap_limit_callback(near_heap_limit, heap_limit.max);
+            isolate.add_near_heap_limit_callback(near_heap_limit, data);
+        }
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::*;
+
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    /// Interrupts JavaScript running in a [`Runtime`](crate::Runtime) from any
+    /// thread.
+    ///
+    /// On the web, scripts run on the page's thread and can't be interrupted.
+    #[derive(Clone)]
+    pub struct TerminationHandle {
+        pub(crate) _private: (),
+    }
+
+    impl TerminationHandle {
+        /// Does nothing on the web, so this always returns `false`.
+        pub fn terminate(&self) -> bool {
+            false
+        }
+    }
+}
+#[cfg(target_arch = "wasm32")]
+pub use wasm::*;
@@ -1,6 +1,9 @@
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::Scope;
+use crate::{
+    ArrayBuffer, BigInt, Date, JsError, JsString, Map, Promise, PropertyKey, Scope, SerdeError, Set,
+    Symbol, TypedArray,
+};
 
 #[derive(Clone)]
 pub enum Value {
@@ -8,10 +11,21 @@ pub enum Value {
     Null,
     Bool(bool),
     Number(f64),
+    BigInt(BigInt),
     String(String),
+    /// A string with lone surrogates, which a `String` can't hold. Every other
+    /// string is a [`Value::String`].
+    IllFormedString(JsString),
+    Symbol(Symbol),
     Array(Array),
     Object(Object),
     Function(Function),
+    Promise(Promise),
+    Date(Date),
+    Map(Map),
+    Set(Set),
+    ArrayBuffer(ArrayBuffer),
+    TypedArray(TypedArray),
 }
 
 impl Value {
@@ -28,46 +42,68 @@ impl Value {
             Self::Bool(value.boolean_value(scope))
         } else if value.is_number() {
             Self::Number(value.number_value(scope).unwrap())
+        } else if value.is_big_int() {
+            Self::BigInt(BigInt::from_v8(value.try_into().unwrap()))
         } else if value.is_string() {
-            // TODO: this impl kinda sucks?
-            let string: v8::Local<v8::String> = value.try_into().unwrap();
-            let mut buffer = [0; 1024];
-            let mut nchars = 0;
-            string.write_utf8(
-                scope,
-                &mut buffer,
-                Some(&mut nchars),
-                v8::WriteOptions::default(),
-            );
-            let string = std::str::from_utf8(&buffer).unwrap().to_owned();
-            Self::String(string.chars().take(nchars).collect())
+            JsString::from_v8(scope, value.try_into().unwrap()).into()
+        } else if value.is_symbol() {
+            Self::Symbol(Symbol::from_v8(scope, value.try_into().unwrap()))
         } else if value.is_function() {
             Self::Function(Function::from_v8(scope, value.try_into().unwrap()))
         } else if value.is_array() {
             Self::Array(Array::from_v8(scope, value.try_into().unwrap()))
+        } else if value.is_promise() {
+            Self::Promise(Promise::from_v8(scope, value.try_into().unwrap()))
+        } else if value.is_date() {
+            Self::Date(Date::from_v8(scope, value.try_into().unwrap()))
+        } else if value.is_map() {
+            Self::Map(Map::from_v8(scope, value.try_into().unwrap()))
+        } else if value.is_set() {
+            Self::Set(Set::from_v8(scope, value.try_into().unwrap()))
+        } else if value.is_array_buffer() {
+            Self::ArrayBuffer(ArrayBuffer::from_v8(scope, value.try_into().unwrap()))
+        } else if value.is_typed_array() {
+            Self::TypedArray(TypedArray::from_v8(scope, value.try_into().unwrap()))
         } else if value.is_object() {
             Self::Object(Object::from_v8(scope, value.try_into().unwrap()))
         } else {
-            todo!("{:?}", value.to_rust_string_lossy(scope))
+            // every primitive type is handled above, and everything else is an
+            // object
+            unreachable!("unknown value: {:?}", value.to_rust_string_lossy(scope))
         }
     }
 
+    /// Returns `None`, with a `RangeError` thrown, if the value is a string
+    /// longer than the engine allows.
     #[cfg(not(target_arch = "wasm32"))]
-    pub(crate) fn to_v8<'s>(&self, scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::Value> {
-        match self {
+    pub(crate) fn to_v8<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+    ) -> Option<v8::Local<'s, v8::Value>> {
+        Some(match self {
             Value::Undefined => v8::undefined(scope).into(),
             Value::Null => v8::null(scope).into(),
             Value::Bool(value) => v8::Boolean::new(scope, *value).into(),
             Value::Number(value) => v8::Number::new(scope, *value).into(),
-            Value::String(value) => v8::String::new(scope, value.as_str()).unwrap().into(),
+            Value::BigInt(value) => value.to_v8(scope).into(),
+            Value::String(value) => crate::string::new_v8_string(scope, value)?.into(),
+            Value::IllFormedString(value) => value.to_v8(scope)?.into(),
+            Value::Symbol(value) => value.to_v8(scope).into(),
             Value::Array(value) => value.to_v8(scope).into(),
             Value::Object(value) => value.to_v8(scope).into(),
             Value::Function(value) => value.to_v8(scope).into(),
-        }
+            Value::Promise(value) => value.to_v8(scope).into(),
+            Value::Date(value) => value.to_v8(scope).into(),
+            Value::Map(value) => value.to_v8(scope).into(),
+            Value::Set(value) => value.to_v8(scope).into(),
+            Value::ArrayBuffer(value) => value.to_v8(scope).into(),
+            Value::TypedArray(value) => value.to_v8(scope).into(),
+        })
     }
 
     #[cfg(target_arch = "wasm32")]
     pub(crate) fn from_web<'s>(value: wasm_bindgen::JsValue) -> Self {
+        use wasm_bindgen::JsCast;
         if value.is_undefined() {
             Self::Undefined
         } else if value.is_null() {
@@ -76,16 +112,38 @@ impl Value {
             Self::Bool(value)
         } else if let Some(value) = value.as_f64() {
             Self::Number(value)
-        } else if let Some(value) = value.as_string() {
-            Self::String(value)
+        } else if value.is_bigint() {
+            Self::BigInt(BigInt::from_web(value.unchecked_into()))
+        } else if value.is_string() {
+            JsString::from_web(value.unchecked_into()).into()
+        } else if value.is_symbol() {
+            Self::Symbol(Symbol::from_web(value.unchecked_into()))
         } else if value.is_function() {
             Self::Function(Function::from_web(value.into()))
         } else if value.is_array() {
             Self::Array(Array::from_web(value.into()))
+        } else if value.is_instance_of::<js_sys::Promise>() {
+            Self::Promise(Promise::from_web(value.into()))
+        } else if value.is_instance_of::<js_sys::Date>() {
+            Self::Date(Date::from_web(value.into()))
+        } else if value.is_instance_of::<js_sys::Map>() {
+            Self::Map(Map::from_web(value.into()))
+        } else if value.is_instance_of::<js_sys::Set>() {
+            Self::Set(Set::from_web(value.into()))
+        } else if value.is_instance_of::<js_sys::ArrayBuffer>() {
+            Self::ArrayBuffer(ArrayBuffer::from_web(value.into()))
         } else if value.is_object() {
-            Self::Object(Object::from_web(value.into()))
+            let object: js_sys::Object = value.into();
+            if js_sys::ArrayBuffer::is_view(&object) {
+                if let Some(array) = TypedArray::from_web(object.clone()) {
+                    return Self::TypedArray(array);
+                }
+            }
+            Self::Object(Object::from_web(object))
         } else {
-            todo!("{:?}", value)
+            // every primitive type is handled above, and everything else is an
+            // object
+            unreachable!("unknown value: {:?}", value)
         }
     }
 
@@ -96,10 +154,19 @@ impl Value {
             Value::Null => wasm_bindgen::JsValue::null(),
             Value::Bool(value) => wasm_bindgen::JsValue::from_bool(*value),
             Value::Number(value) => wasm_bindgen::JsValue::from_f64(*value),
+            Value::BigInt(value) => value.to_web().into(),
             Value::String(value) => wasm_bindgen::JsValue::from_str(value.as_str()),
+            Value::IllFormedString(value) => value.to_web().into(),
+            Value::Symbol(value) => value.to_web().into(),
             Value::Array(value) => value.to_web().into(),
             Value::Object(value) => value.to_web().into(),
             Value::Function(value) => value.to_web().into(),
+            Value::Promise(value) => value.to_web().into(),
+            Value::Date(value) => value.to_web().into(),
+            Value::Map(value) => value.to_web().into(),
+            Value::Set(value) => value.to_web().into(),
+            Value::ArrayBuffer(value) => value.to_web().into(),
+            Value::TypedArray(value) => value.to_web().into(),
         }
     }
 
@@ -119,8 +186,16 @@ impl Value {
         matches!(self, Self::Number(..))
     }
 
+    pub fn is_bigint(self) -> bool {
+        matches!(self, Self::BigInt(..))
+    }
+
     pub fn is_string(self) -> bool {
-        matches!(self, Self::String(..))
+        matches!(self, Self::String(..) | Self::IllFormedString(..))
+    }
+
+    pub fn is_symbol(self) -> bool {
+        matches!(self, Self::Symbol(..))
     }
 
     pub fn is_array(self) -> bool {
@@ -135,6 +210,30 @@ impl Value {
         matches!(self, Self::Function(..))
     }
 
+    pub fn is_promise(self) -> bool {
+        matches!(self, Self::Promise(..))
+    }
+
+    pub fn is_date(self) -> bool {
+        matches!(self, Self::Date(..))
+    }
+
+    pub fn is_map(self) -> bool {
+        matches!(self, Self::Map(..))
+    }
+
+    pub fn is_set(self) -> bool {
+        matches!(self, Self::Set(..))
+    }
+
+    pub fn is_array_buffer(self) -> bool {
+        matches!(self, Self::ArrayBuffer(..))
+    }
+
+    pub fn is_typed_array(self) -> bool {
+        matches!(self, Self::TypedArray(..))
+    }
+
     pub fn into_bool(self) -> Option<bool> {
         if let Value::Bool(bool) = self {
             Some(bool)
@@ -151,9 +250,36 @@ impl Value {
         }
     }
 
+    pub fn into_bigint(self) -> Option<BigInt> {
+        if let Value::BigInt(bigint) = self {
+            Some(bigint)
+        } else {
+            None
+        }
+    }
+
+    /// The string, with lone surrogates replaced by `U+FFFD`. Use
+    /// [`Value::into_js_string`] to keep them.
     pub fn into_string(self) -> Option<String> {
-        if let Value::String(string) = self {
-            Some(string)
+        match self {
+            Value::String(string) => Some(string),
+            Value::IllFormedString(string) => Some(string.into_string()),
+            _ => None,
+        }
+    }
+
+    /// The string, keeping any lone surrogates.
+    pub fn into_js_string(self) -> Option<JsString> {
+        match self {
+            Value::String(string) => Some(JsString::new(string)),
+            Value::IllFormedString(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn into_symbol(self) -> Option<Symbol> {
+        if let Value::Symbol(symbol) = self {
+            Some(symbol)
         } else {
             None
         }
@@ -183,8 +309,73 @@ impl Value {
         }
     }
 
-    pub fn into_json(self, scope: &mut Scope) -> Option<serde_json::Value> {
-        match self {
+    pub fn into_promise(self) -> Option<Promise> {
+        if let Value::Promise(promise) = self {
+            Some(promise)
+        } else {
+            None
+        }
+    }
+
+    pub fn into_date(self) -> Option<Date> {
+        if let Value::Date(date) = self {
+            Some(date)
+        } else {
+            None
+        }
+    }
+
+    pub fn into_map(self) -> Option<Map> {
+        if let Value::Map(map) = self {
+            Some(map)
+        } else {
+            None
+        }
+    }
+
+    pub fn into_set(self) -> Option<Set> {
+        if let Value::Set(set) = self {
+            Some(set)
+        } else {
+            None
+        }
+    }
+
+    pub fn into_array_buffer(self) -> Option<ArrayBuffer> {
+        if let Value::ArrayBuffer(buffer) = self {
+            Some(buffer)
+        } else {
+            None
+        }
+    }
+
+    pub fn into_typed_array(self) -> Option<TypedArray> {
+        if let Value::TypedArray(array) = self {
+            Some(array)
+        } else {
+            None
+        }
+    }
+
+    /// Creates a new `Error` object, suitable for throwing from a [`Function`].
+    #[allow(unused_variables)]
+    pub fn error(scope: &mut Scope, message: &str) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let message = v8::String::new(scope, message).unwrap();
+                let error = v8::Exception::error(scope, message);
+                Value::from_v8(scope, error)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Value::from_web(js_sys::Error::new(message).into())
+        }
+    }
+
+    pub fn into_json(self, scope: &mut Scope) -> Result<Option<serde_json::Value>, JsError> {
+        Ok(match self {
             Self::Undefined => None,
             Self::Null => Some(serde_json::Value::Null),
             Self::Bool(value) => Some(serde_json::Value::Bool(value)),
@@ -193,17 +384,29 @@ impl Value {
                     serde_json::from_str::<serde_json::Number>(&format!("{}", value as u64)).unwrap()
                 } else if value as i64 as f64 == value {
                     serde_json::from_str::<serde_json::Number>(&format!("{}", value as i64)).unwrap()
+                } else if let Some(number) = serde_json::Number::from_f64(value) {
+                    number
                 } else {
-                    serde_json::Number::from_f64(value).unwrap()
+                    return Ok(Some(serde_json::Value::Null));
                 };
                 Some(serde_json::Value::Number(number))
             }
+            // JSON numbers hold integers up to 64 bits, and larger ones are
+            // written as strings
+            Self::BigInt(value) => Some(if let Some(value) = value.to_i64() {
+                serde_json::Value::from(value)
+            } else if let Some(value) = value.to_u64() {
+                serde_json::Value::from(value)
+            } else {
+                serde_json::Value::String(value.to_string())
+            }),
             Self::String(value) => Some(serde_json::Value::String(value)),
+            Self::IllFormedString(value) => Some(serde_json::Value::String(value.into_string())),
             Self::Array(value) => {
                 let mut array = vec![];
                 for i in 0..value.length(scope) {
-                    let item = value.get(scope, i);
-                    if let Some(json) = item.into_json(scope) {
+                    let item = value.get(scope, i)?;
+                    if let Some(json) = item.into_json(scope)? {
                         array.push(json);
                     } else {
                         array.push(serde_json::Value::Null);
@@ -213,59 +416,193 @@ impl Value {
             }
             Self::Object(value) => {
                 let mut map = serde_json::Map::new();
-                for key in value.keys(scope) {
-                    if let Some(value) = value.get(scope, &key).into_json(scope) {
+                for key in value.keys(scope)? {
+                    if let Some(value) = value.get(scope, &key)?.into_json(scope)? {
                         map.insert(key, value);
                     }
                 }
                 Some(serde_json::Value::Object(map))
             }
-            Self::Function(..) => None,
-        }
+            Self::Symbol(..) | Self::Function(..) => None,
+            Self::Promise(..) => Some(serde_json::Value::Object(serde_json::Map::new())),
+            // dates become ISO strings like in `JSON.stringify`, which chrono
+            // and time can deserialize
+            Self::Date(value) => Some(
+                value
+                    .to_iso_string(scope)
+                    .map(serde_json::Value::String)
+                    .unwrap_or(serde_json::Value::Null),
+            ),
+            // maps with primitive keys become objects, which serde_json can
+            // deserialize into maps with string or integer keys, and other
+            // maps become arrays of entries
+            Self::Map(value) => {
+                let mut entries = vec![];
+                for (key, value) in value.entries(scope)? {
+                    let key = key.into_json(scope)?.unwrap_or(serde_json::Value::Null);
+                    let value = value.into_json(scope)?.unwrap_or(serde_json::Value::Null);
+                    entries.push((key, value));
+                }
+                let primitive_keys = entries.iter().all(|(key, _)| {
+                    matches!(
+                        key,
+                        serde_json::Value::String(..)
+                            | serde_json::Value::Number(..)
+                            | serde_json::Value::Bool(..)
+                    )
+                });
+                Some(if primitive_keys {
+                    serde_json::Value::Object(
+                        entries
+                            .into_iter()
+                            .map(|(key, value)| match key {
+                                serde_json::Value::String(key) => (key, value),
+                                key => (key.to_string(), value),
+                            })
+                            .collect(),
+                    )
+                } else {
+                    serde_json::Value::Array(
+                        entries
+                            .into_iter()
+                            .map(|(key, value)| serde_json::Value::Array(vec![key, value]))
+                            .collect(),
+                    )
+                })
+            }
+            Self::Set(value) => {
+                let mut array = vec![];
+                for item in value.values(scope)? {
+                    array.push(item.into_json(scope)?.unwrap_or(serde_json::Value::Null));
+                }
+                Some(serde_json::Value::Array(array))
+            }
+            // binary data becomes an array of its elements, so that it can be
+            // deserialized into a `Vec`
+            Self::ArrayBuffer(value) => Some(serde_json::Value::Array(
+                value
+                    .to_vec(scope)
+                    .into_iter()
+                    .map(serde_json::Value::from)
+                    .collect(),
+            )),
+            Self::TypedArray(value) => Some(serde_json::Value::Array(value.to_json(scope))),
+        })
+    }
+
+    pub fn from_json(scope: &mut Scope, json: serde_json::Value) -> Result<Self, JsError> {
+        Self::from_json_with_options(scope, json, &SerializeOptions::default())
     }
 
-    pub fn from_json(scope: &mut Scope, json: serde_json::Value) -> Self {
-        match json {
+    pub fn from_json_with_options(
+        scope: &mut Scope,
+        json: serde_json::Value,
+        options: &SerializeOptions,
+    ) -> Result<Self, JsError> {
+        Ok(match json {
             serde_json::Value::Null => Self::Null,
             serde_json::Value::Bool(value) => Self::Bool(value),
-            serde_json::Value::Number(value) => Self::Number(value.as_f64().unwrap()),
+            serde_json::Value::Number(value) => {
+                let bigint = if let Some(value) = value.as_i64() {
+                    Some(BigInt::from(value))
+                } else {
+                    value.as_u64().map(BigInt::from)
+                };
+                match bigint {
+                    Some(bigint)
+                        if options.large_integers_as_bigint
+                            && bigint.to_f64().abs() > MAX_SAFE_INTEGER =>
+                    {
+                        Self::BigInt(bigint)
+                    }
+                    _ => Self::Number(value.as_f64().unwrap()),
+                }
+            }
             serde_json::Value::String(value) => Self::String(value),
             serde_json::Value::Array(value) => {
                 let array = Array::new(scope);
                 for item in value {
-                    let item = Value::from_json(scope, item);
-                    array.push(scope, item);
+                    let item = Value::from_json_with_options(scope, item, options)?;
+                    array.push(scope, item)?;
                 }
                 Self::Array(array)
             }
             serde_json::Value::Object(value) => {
                 let object = Object::new(scope);
                 for (key, value) in value {
-                    let item = Value::from_json(scope, value);
-                    object.set(scope, &key, item);
+                    let item = Value::from_json_with_options(scope, value, options)?;
+                    object.set(scope, &key, item)?;
                 }
                 Self::Object(object)
             }
-        }
+        })
     }
 
-    pub fn serialize<T: Serialize>(scope: &mut Scope, value: &T) -> Option<Self> {
-        if let Ok(json) = serde_json::to_value(&value) {
-            Some(Self::from_json(scope, json))
-        } else {
-            None
+    pub fn serialize<T: Serialize>(scope: &mut Scope, value: &T) -> Result<Self, SerdeError> {
+        Self::serialize_with_options(scope, value, &SerializeOptions::default())
+    }
+
+    pub fn serialize_with_options<T: Serialize>(
+        scope: &mut Scope,
+        value: &T,
+        options: &SerializeOptions,
+    ) -> Result<Self, SerdeError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let scope = &mut v8::TryCatch::new(scope);
+                let value = value.serialize(crate::ser::Serializer::new(scope, options));
+                if scope.has_caught() {
+                    return Err(SerdeError::Exception(JsError::from_try_catch(scope)));
+                }
+                Ok(Value::from_v8(scope, value?))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let value = value.serialize(crate::ser::Serializer::new(&mut (), options))?;
+            Ok(Value::from_web(value))
         }
     }
 
-    pub fn deserialize<T: DeserializeOwned>(self, scope: &mut Scope) -> Option<T> {
-        if let Some(json) = self.into_json(scope) {
-            serde_json::from_value::<T>(json).ok()
-        } else {
-            None
+    #[allow(unused_variables)]
+    pub fn deserialize<T: DeserializeOwned>(self, scope: &mut Scope) -> Result<T, SerdeError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| {
+                let scope = &mut v8::TryCatch::new(scope);
+                let Some(value) = self.to_v8(scope) else {
+                    return Err(SerdeError::Exception(JsError::from_try_catch(scope)));
+                };
+                let result = T::deserialize(crate::de::Deserializer::new(scope, value));
+                if scope.has_caught() {
+                    return Err(SerdeError::Exception(JsError::from_try_catch(scope)));
+                }
+                result
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            T::deserialize(crate::de::Deserializer::new(&mut (), self.to_web()))
         }
     }
 }
 
+/// The largest integer a number can hold exactly, `Number.MAX_SAFE_INTEGER`.
+pub(crate) const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// Controls how Rust values are converted by [`Value::serialize_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOptions {
+    /// Converts integers that a number can't hold exactly, such as large IDs,
+    /// to `BigInt`s instead. They are converted back when deserialized.
+    pub large_integers_as_bigint: bool,
+    /// Converts Rust maps whose keys aren't all strings, such as
+    /// `HashMap<u32, _>`, to `Map`s instead of objects, so the keys keep their
+    /// type.
+    pub non_string_keys_as_map: bool,
+}
+
 impl std::fmt::Debug for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -273,10 +610,19 @@ impl std::fmt::Debug for Value {
             Self::Null => f.write_str("null"),
             Self::Bool(value) => value.fmt(f),
             Self::Number(value) => value.fmt(f),
+            Self::BigInt(value) => value.fmt(f),
             Self::String(value) => value.fmt(f),
+            Self::IllFormedString(value) => value.fmt(f),
+            Self::Symbol(value) => value.fmt(f),
             Self::Array(value) => value.fmt(f),
             Self::Object(value) => value.fmt(f),
             Self::Function(value) => value.fmt(f),
+            Self::Promise(value) => value.fmt(f),
+            Self::Date(value) => value.fmt(f),
+            Self::Map(value) => value.fmt(f),
+            Self::Set(value) => value.fmt(f),
+            Self::ArrayBuffer(value) => value.fmt(f),
+            Self::TypedArray(value) => value.fmt(f),
         }
     }
 }
@@ -333,16 +679,84 @@ impl Array {
         }
     }
 
+    /// Creates an array holding `values`. Fails if a string is longer than the
+    /// engine allows.
     #[allow(unused_variables)]
-    pub fn length(&self, scope: &mut Scope) -> u32 {
+    pub fn from_slice(scope: &mut Scope, values: &[Value]) -> Result<Self, JsError> {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let array = self.array.clone();
-            scope.enter(move |scope| {
-                let array = v8::Local::new(scope, array);
-                array.length()
+            scope.try_enter(|scope| {
+                let elements = values
+                    .iter()
+                    .map(|value| value.to_v8(scope))
+                    .collect::<Option<Vec<_>>>()?;
+                let array = v8::Array::new_with_elements(scope, &elements);
+                Some(Self {
+                    array: v8::Global::new(scope, array),
+                })
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(Self {
+                array: values.iter().map(Value::to_web).collect(),
             })
         }
+    }
+
+    /// Creates an array holding the values of `iter`.
+    pub fn from_iter(
+        scope: &mut Scope,
+        iter: impl IntoIterator<Item = Value>,
+    ) -> Result<Self, JsError> {
+        let values: Vec<_> = iter.into_iter().collect();
+        Self::from_slice(scope, &values)
+    }
+
+    /// Converts every element at once.
+    #[allow(unused_variables)]
+    pub fn to_vec(&self, scope: &mut Scope) -> Result<Vec<Value>, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.try_enter(|scope| {
+                let array = self.to_v8(scope);
+                let mut values = Vec::with_capacity(array.length() as usize);
+                for i in 0..array.length() {
+                    let value = array.get_index(scope, i)?;
+                    values.push(Value::from_v8(scope, value));
+                }
+                Some(values)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let mut values = Vec::with_capacity(self.array.length() as usize);
+            for i in 0..self.array.length() {
+                let value = js_sys::Reflect::get_u32(&self.array, i).map_err(JsError::from_web)?;
+                values.push(Value::from_web(value));
+            }
+            Ok(values)
+        }
+    }
+
+    /// Iterates over the elements, reading each one as it's reached. Elements
+    /// added after the iterator is created are not visited.
+    pub fn iter<'s, 'a, 'b>(&'s self, scope: &'s mut Scope<'a, 'b>) -> ArrayIter<'s, 'a, 'b> {
+        let length = self.length(scope);
+        ArrayIter {
+            array: self,
+            scope,
+            index: 0,
+            length,
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn length(&self, scope: &mut Scope) -> u32 {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.enter(|scope| self.to_v8(scope).length())
+        }
         #[cfg(target_arch = "wasm32")]
         {
             self.array.length()
@@ -350,61 +764,212 @@ impl Array {
     }
 
     #[allow(unused_variables)]
-    pub fn get(&self, scope: &mut Scope, index: u32) -> Value {
+    pub fn get(&self, scope: &mut Scope, index: u32) -> Result<Value, JsError> {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let array = self.array.clone();
-            scope.enter(move |scope| {
-                let array = v8::Local::new(scope, array);
-                let key = v8::Number::new(scope, index as f64);
-                if let Some(value) = array.get(scope, key.into()) {
-                    Value::from_v8(scope, value)
-                } else {
-                    Value::Undefined
+            scope.try_enter(|scope| {
+                let array = self.to_v8(scope);
+                let value = array.get_index(scope, index)?;
+                Some(Value::from_v8(scope, value))
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::Reflect::get_u32(&self.array, index)
+                .map(Value::from_web)
+                .map_err(JsError::from_web)
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn set(&self, scope: &mut Scope, index: u32, value: Value) -> Result<(), JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.try_enter(move |scope| {
+                let array = self.to_v8(scope);
+                let value = value.to_v8(scope)?;
+                array.set_index(scope, index, value).map(|_| ())
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::Reflect::set_u32(&self.array, index, &value.to_web())
+                .map(|_| ())
+                .map_err(JsError::from_web)
+        }
+    }
+
+    pub fn push(&self, scope: &mut Scope, value: Value) -> Result<(), JsError> {
+        self.extend(scope, [value])
+    }
+
+    /// Appends every value of `values`.
+    #[allow(unused_variables)]
+    pub fn extend(
+        &self,
+        scope: &mut Scope,
+        values: impl IntoIterator<Item = Value>,
+    ) -> Result<(), JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            scope.try_enter(move |scope| {
+                let array = self.to_v8(scope);
+                for value in values {
+                    let length = array.length();
+                    let value = value.to_v8(scope)?;
+                    array.set_index(scope, length, value)?;
                 }
+                Some(())
             })
         }
         #[cfg(target_arch = "wasm32")]
         {
-            Value::from_web(self.array.get(index))
+            for value in values {
+                let length = self.array.length();
+                js_sys::Reflect::set_u32(&self.array, length, &value.to_web())
+                    .map_err(JsError::from_web)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Removes the last element, or returns `None` if the array is empty.
+    pub fn pop(&self, scope: &mut Scope) -> Result<Option<Value>, JsError> {
+        let length = self.length(scope);
+        if length == 0 {
+            return Ok(None);
         }
+        self.remove(scope, length - 1)
+    }
+
+    /// Inserts `value` at `index`, shifting the later elements up. Like
+    /// `splice`, an index past the end appends.
+    pub fn insert(&self, scope: &mut Scope, index: u32, value: Value) -> Result<(), JsError> {
+        self.splice(scope, index as i64, 0, [value]).map(|_| ())
     }
 
+    /// Removes the element at `index`, shifting the later elements down.
+    /// Returns `None` if `index` is past the end.
+    pub fn remove(&self, scope: &mut Scope, index: u32) -> Result<Option<Value>, JsError> {
+        let removed = self.splice(scope, index as i64, 1, [])?;
+        if removed.length(scope) == 0 {
+            Ok(None)
+        } else {
+            removed.get(scope, 0).map(Some)
+        }
+    }
+
+    /// Removes `delete_count` elements from `start` and inserts `items` in
+    /// their place, like `Array.prototype.splice`. A negative `start` counts
+    /// back from the end. Returns the removed elements.
+    pub fn splice(
+        &self,
+        scope: &mut Scope,
+        start: i64,
+        delete_count: u32,
+        items: impl IntoIterator<Item = Value>,
+    ) -> Result<Array, JsError> {
+        let mut args = vec![
+            Value::Number(start as f64),
+            Value::Number(delete_count as f64),
+        ];
+        args.extend(items);
+        let removed = self.call_intrinsic(scope, "Array.prototype.splice", &args)?;
+        removed
+            .into_array()
+            .ok_or_else(|| JsError::new("TypeError", "splice didn't return an array"))
+    }
+
+    /// A shallow copy of the elements from `start` up to `end`, like
+    /// `Array.prototype.slice`. Negative indices count back from the end.
+    pub fn slice(&self, scope: &mut Scope, start: i64, end: i64) -> Result<Array, JsError> {
+        let args = [Value::Number(start as f64), Value::Number(end as f64)];
+        let slice = self.call_intrinsic(scope, "Array.prototype.slice", &args)?;
+        slice
+            .into_array()
+            .ok_or_else(|| JsError::new("TypeError", "slice didn't return an array"))
+    }
+
+    /// Shortens the array to `length` elements. Does nothing if it's already
+    /// shorter.
     #[allow(unused_variables)]
-    pub fn set(&self, scope: &mut Scope, index: u32, value: Value) {
+    pub fn truncate(&self, scope: &mut Scope, length: u32) -> Result<(), JsError> {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let array = self.array.clone();
-            scope.enter(move |scope| {
-                let array = v8::Local::new(scope, array);
-                let key = v8::Number::new(scope, index as f64);
-                let value = value.to_v8(scope);
-                array.set(scope, key.into(), value);
+            scope.try_enter(|scope| {
+                let array = self.to_v8(scope);
+                if length < array.length() {
+                    let key = v8::String::new(scope, "length")?;
+                    let value = v8::Integer::new_from_unsigned(scope, length);
+                    array.set(scope, key.into(), value.into())?;
+                }
+                Some(())
             })
         }
         #[cfg(target_arch = "wasm32")]
         {
-            self.array.set(index, value.to_web())
+            if length < self.array.length() {
+                js_sys::Reflect::set(&self.array, &"length".into(), &length.into())
+                    .map_err(JsError::from_web)?;
+            }
+            Ok(())
         }
     }
 
+    /// Calls an `Array.prototype` method as it was when the context was
+    /// created, so scripts that replace it don't change the result.
     #[allow(unused_variables)]
-    pub fn push(&self, scope: &mut Scope, value: Value) {
+    fn call_intrinsic(
+        &self,
+        scope: &mut Scope,
+        path: &'static str,
+        args: &[Value],
+    ) -> Result<Value, JsError> {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let array = self.array.clone();
-            scope.enter(move |scope| {
-                let array = v8::Local::new(scope, array);
-                let length = array.length();
-                let key = v8::Number::new(scope, length as f64);
-                let value = value.to_v8(scope);
-                array.set(scope, key.into(), value);
-            });
+            scope.try_enter(|scope| {
+                let array = self.to_v8(scope);
+                let args = args
+                    .iter()
+                    .map(|arg| arg.to_v8(scope))
+                    .collect::<Option<Vec<_>>>()?;
+                let result = crate::intrinsics::call(scope, path, array.into(), &args)?;
+                Some(Value::from_v8(scope, result))
+            })
         }
         #[cfg(target_arch = "wasm32")]
         {
-            self.array.push(&value.to_web());
+            let args: js_sys::Array = args.iter().map(Value::to_web).collect();
+            crate::intrinsics::call(path, &self.array, &args)
+                .map(Value::from_web)
+                .map_err(JsError::from_web)
+        }
+    }
+}
+
+/// Iterator over the elements of an [`Array`], created by [`Array::iter`].
+pub struct ArrayIter<'s, 'a, 'b> {
+    array: &'s Array,
+    scope: &'s mut Scope<'a, 'b>,
+    index: u32,
+    length: u32,
+}
+
+impl Iterator for ArrayIter<'_, '_, '_> {
+    type Item = Result<Value, JsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.length {
+            return None;
         }
+        let value = self.array.get(self.scope, self.index);
+        self.index += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.length - self.index) as usize;
+        (remaining, Some(remaining))
     }
 }
 
@@ -473,83 +1038,404 @@ impl Object {
     }
 
     #[allow(unused_variables)]
-    pub fn get(&self, scope: &mut Scope, name: &str) -> Value {
+    pub fn get(&self, scope: &mut Scope, key: impl Into<PropertyKey>) -> Result<Value, JsError> {
+        let key = key.into();
         #[cfg(not(target_arch = "wasm32"))]
         {
             let object = self.object.clone();
-            scope.enter(move |scope| {
+            scope.try_enter(move |scope| {
                 let object = v8::Local::new(scope, object);
-                let name = v8::String::new(scope, name).unwrap();
-                let value = object.get(scope, name.into());
-                if let Some(value) = value {
-                    Value::from_v8(scope, value)
-                } else {
-                    Value::Undefined
-                }
+                let key = key.to_v8(scope)?;
+                let value = object.get(scope, key)?;
+                Some(Value::from_v8(scope, value))
             })
         }
         #[cfg(target_arch = "wasm32")]
         {
-            Value::from_web(
-                js_sys::Reflect::get(&self.object, &wasm_bindgen::JsValue::from(name)).unwrap(),
-            )
+            js_sys::Reflect::get(&self.object, &key.to_web())
+                .map(Value::from_web)
+                .map_err(JsError::from_web)
         }
     }
 
     #[allow(unused_variables)]
-    pub fn set(&self, scope: &mut Scope, name: &str, value: Value) {
+    pub fn set(
+        &self,
+        scope: &mut Scope,
+        key: impl Into<PropertyKey>,
+        value: Value,
+    ) -> Result<(), JsError> {
+        let key = key.into();
         #[cfg(not(target_arch = "wasm32"))]
         {
             let object = self.object.clone();
-            scope.enter(move |scope| {
+            scope.try_enter(move |scope| {
                 let object = v8::Local::new(scope, object);
-                let name = v8::String::new(scope, name).unwrap();
-                let value = value.to_v8(scope);
-                object.set(scope, name.into(), value).unwrap();
+                let key = key.to_v8(scope)?;
+                let value = value.to_v8(scope)?;
+                object.set(scope, key, value).map(|_| ())
             })
         }
         #[cfg(target_arch = "wasm32")]
         {
-            js_sys::Reflect::set(
-                &self.object,
-                &wasm_bindgen::JsValue::from(name),
-                &value.to_web(),
-            )
-            .unwrap();
+            js_sys::Reflect::set(&self.object, &key.to_web(), &value.to_web())
+                .map(|_| ())
+                .map_err(JsError::from_web)
         }
     }
 
+    /// The object's own enumerable string keys, like `Object.keys`.
     #[allow(unused_variables)]
-    pub fn keys(&self, scope: &mut Scope) -> Vec<String> {
+    pub fn keys(&self, scope: &mut Scope) -> Result<Vec<String>, JsError> {
         #[cfg(not(target_arch = "wasm32"))]
         {
             let object = self.object.clone();
-            scope.enter(move |scope| {
+            scope.try_enter(move |scope| {
                 let object = v8::Local::new(scope, object);
-                let names = object.get_own_property_names(scope, v8::GetPropertyNamesArgs::default()).unwrap();
+                let names = object.get_own_property_names(
+                    scope,
+                    v8::GetPropertyNamesArgs {
+                        key_conversion: v8::KeyConversionMode::ConvertToString,
+                        ..Default::default()
+                    },
+                )?;
                 let mut keys = vec![];
                 for i in 0..names.length() {
-                    let i_key = v8::Number::new(scope, i as f64);
-                    if let Some(name) = names.get(scope, i_key.into()) {
-                        let name = Value::from_v8(scope, name);
-                        if let Some(name) = name.into_string() {
-                            keys.push(name);
-                        }
-                    }
+                    let name = names.get_index(scope, i)?;
+                    keys.push(name.to_rust_string_lossy(scope));
                 }
-                keys
+                Some(keys)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(js_sys::Object::keys(&self.object)
+                .iter()
+                .filter_map(|key| key.as_string())
+                .collect())
+        }
+    }
+
+    /// The object's own enumerable keys, including the symbols that
+    /// [`Object::keys`] leaves out.
+    #[allow(unused_variables)]
+    pub fn property_keys(&self, scope: &mut Scope) -> Result<Vec<PropertyKey>, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let object = self.object.clone();
+            scope.try_enter(move |scope| {
+                let object = v8::Local::new(scope, object);
+                let names = object.get_own_property_names(
+                    scope,
+                    v8::GetPropertyNamesArgs {
+                        property_filter: v8::PropertyFilter::ONLY_ENUMERABLE,
+                        key_conversion: v8::KeyConversionMode::ConvertToString,
+                        ..Default::default()
+                    },
+                )?;
+                let mut keys = vec![];
+                for i in 0..names.length() {
+                    let name = names.get_index(scope, i)?;
+                    keys.extend(PropertyKey::from_v8(scope, name));
+                }
+                Some(keys)
             })
         }
         #[cfg(target_arch = "wasm32")]
         {
             let mut keys = vec![];
-            let object_keys = js_sys::Reflect::own_keys(&self.object.clone().into()).unwrap();
+            let object_keys =
+                js_sys::Reflect::own_keys(&self.object.clone().into()).map_err(JsError::from_web)?;
             for item in object_keys {
-                if let Some(name) = item.as_string() {
-                    keys.push(name);
+                // read from the descriptor, since objects without a prototype
+                // have no `propertyIsEnumerable`
+                let descriptor = js_sys::Reflect::get_own_property_descriptor(&self.object, &item)
+                    .map_err(JsError::from_web)?;
+                if descriptor.is_undefined() {
+                    continue;
                 }
+                let enumerable =
+                    js_sys::Reflect::get(&descriptor, &wasm_bindgen::JsValue::from("enumerable"))
+                        .map_err(JsError::from_web)?;
+                if enumerable.is_truthy() {
+                    keys.extend(PropertyKey::from_web(item));
+                }
+            }
+            Ok(keys)
+        }
+    }
+
+    /// The values of the object's own enumerable string keys, like
+    /// `Object.values`.
+    pub fn values(&self, scope: &mut Scope) -> Result<Vec<Value>, JsError> {
+        Ok(self
+            .entries(scope)?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect())
+    }
+
+    /// The object's own enumerable string keyed properties, like
+    /// `Object.entries`.
+    pub fn entries(&self, scope: &mut Scope) -> Result<Vec<(String, Value)>, JsError> {
+        let keys = self.keys(scope)?;
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value = self.get(scope, &key)?;
+            entries.push((key, value));
+        }
+        Ok(entries)
+    }
+
+    /// The number of own enumerable string keys.
+    pub fn len(&self, scope: &mut Scope) -> Result<usize, JsError> {
+        Ok(self.keys(scope)?.len())
+    }
+
+    pub fn is_empty(&self, scope: &mut Scope) -> Result<bool, JsError> {
+        Ok(self.len(scope)? == 0)
+    }
+
+    /// Whether the object or its prototype chain has the property, like the
+    /// `in` operator.
+    #[allow(unused_variables)]
+    pub fn has(&self, scope: &mut Scope, key: impl Into<PropertyKey>) -> Result<bool, JsError> {
+        let key = key.into();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let object = self.object.clone();
+            scope.try_enter(move |scope| {
+                let object = v8::Local::new(scope, object);
+                let key = key.to_v8(scope)?;
+                object.has(scope, key)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::Reflect::has(&self.object, &key.to_web()).map_err(JsError::from_web)
+        }
+    }
+
+    /// Whether the object itself has the property, like `Object.hasOwn`.
+    #[allow(unused_variables)]
+    pub fn has_own(&self, scope: &mut Scope, key: impl Into<PropertyKey>) -> Result<bool, JsError> {
+        let key = key.into();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let object = self.object.clone();
+            scope.try_enter(move |scope| {
+                let object = v8::Local::new(scope, object);
+                let key = key.to_v8(scope)?.try_into().ok()?;
+                object.has_own_property(scope, key)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::Reflect::get_own_property_descriptor(&self.object, &key.to_web())
+                .map(|descriptor| !descriptor.is_undefined())
+                .map_err(JsError::from_web)
+        }
+    }
+
+    /// Deletes the property, like `Reflect.deleteProperty`. Returns `false`
+    /// if the property is non-configurable.
+    #[allow(unused_variables)]
+    pub fn delete(&self, scope: &mut Scope, key: impl Into<PropertyKey>) -> Result<bool, JsError> {
+        let key = key.into();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let object = self.object.clone();
+            scope.try_enter(move |scope| {
+                let object = v8::Local::new(scope, object);
+                let key = key.to_v8(scope)?;
+                object.delete(scope, key)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::Reflect::delete_property(&self.object, &key.to_web()).map_err(JsError::from_web)
+        }
+    }
+
+    /// Defines a data property with the given attributes, like
+    /// `Reflect.defineProperty`. Returns `false` if the property can't be
+    /// defined, e.g. when it exists and is non-configurable or the object is
+    /// not extensible.
+    #[allow(unused_variables)]
+    pub fn define_property(
+        &self,
+        scope: &mut Scope,
+        key: impl Into<PropertyKey>,
+        value: Value,
+        attributes: PropertyAttributes,
+    ) -> Result<bool, JsError> {
+        let key = key.into();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let object = self.object.clone();
+            scope.try_enter(move |scope| {
+                let object = v8::Local::new(scope, object);
+                let key = key.to_v8(scope)?;
+                let value = value.to_v8(scope)?;
+                let mut descriptor =
+                    v8::PropertyDescriptor::new_from_value_writable(value, attributes.writable);
+                descriptor.set_enumerable(attributes.enumerable);
+                descriptor.set_configurable(attributes.configurable);
+                object.define_property(scope, key.try_into().ok()?, &descriptor)
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use wasm_bindgen::JsValue;
+            let descriptor = js_sys::Object::new();
+            for (name, value) in [
+                ("value", value.to_web()),
+                ("writable", JsValue::from(attributes.writable)),
+                ("enumerable", JsValue::from(attributes.enumerable)),
+                ("configurable", JsValue::from(attributes.configurable)),
+            ] {
+                js_sys::Reflect::set(&descriptor, &JsValue::from(name), &value)
+                    .map_err(JsError::from_web)?;
+            }
+            js_sys::Reflect::define_property(&self.object, &key.to_web(), &descriptor)
+                .map_err(JsError::from_web)
+        }
+    }
+
+    /// Like `Object.freeze`.
+    #[allow(unused_variables)]
+    pub fn freeze(&self, scope: &mut Scope) -> Result<(), JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.set_integrity_level(scope, v8::IntegrityLevel::Frozen)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::Object::freeze(&self.object);
+            Ok(())
+        }
+    }
+
+    /// Like `Object.seal`.
+    #[allow(unused_variables)]
+    pub fn seal(&self, scope: &mut Scope) -> Result<(), JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.set_integrity_level(scope, v8::IntegrityLevel::Sealed)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::Object::seal(&self.object);
+            Ok(())
+        }
+    }
+
+    /// Like `Object.preventExtensions`.
+    #[allow(unused_variables)]
+    pub fn prevent_extensions(&self, scope: &mut Scope) -> Result<(), JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.call_intrinsic(scope, "Object.preventExtensions").map(|_| ())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            js_sys::Object::prevent_extensions(&self.object);
+            Ok(())
+        }
+    }
+
+    /// Like `Object.isFrozen`.
+    #[allow(unused_variables)]
+    pub fn is_frozen(&self, scope: &mut Scope) -> Result<bool, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.call_intrinsic(scope, "Object.isFrozen")
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(js_sys::Object::is_frozen(&self.object))
+        }
+    }
+
+    /// Like `Object.isSealed`.
+    #[allow(unused_variables)]
+    pub fn is_sealed(&self, scope: &mut Scope) -> Result<bool, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.call_intrinsic(scope, "Object.isSealed")
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(js_sys::Object::is_sealed(&self.object))
+        }
+    }
+
+    /// Like `Object.isExtensible`.
+    #[allow(unused_variables)]
+    pub fn is_extensible(&self, scope: &mut Scope) -> Result<bool, JsError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.call_intrinsic(scope, "Object.isExtensible")
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(js_sys::Object::is_extensible(&self.object))
+        }
+    }
+
+    /// Throws a `TypeError` if the level can't be set, like `Object.freeze`.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn set_integrity_level(
+        &self,
+        scope: &mut Scope,
+        level: v8::IntegrityLevel,
+    ) -> Result<(), JsError> {
+        let object = self.object.clone();
+        scope.try_enter(move |scope| {
+            let object = v8::Local::new(scope, object);
+            if !object.set_integrity_level(scope, level)? {
+                let message = v8::String::new(scope, "cannot change the object's integrity level")?;
+                let exception = v8::Exception::type_error(scope, message);
+                scope.throw_exception(exception);
             }
-            keys
+            Some(())
+        })
+    }
+
+    /// Calls an `Object` builtin that the engine has no direct API for, as it
+    /// was when the context was created.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn call_intrinsic(&self, scope: &mut Scope, path: &'static str) -> Result<bool, JsError> {
+        let object = self.object.clone();
+        scope.try_enter(move |scope| {
+            let object = v8::Local::new(scope, object);
+            let undefined = v8::undefined(scope).into();
+            let result = crate::intrinsics::call(scope, path, undefined, &[object.into()])?;
+            Some(result.is_true())
+        })
+    }
+}
+
+/// The attributes of a data property defined with [`Object::define_property`].
+/// Like in JavaScript, every attribute defaults to `false`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PropertyAttributes {
+    /// Whether the value can be changed by assignment.
+    pub writable: bool,
+    /// Whether the property shows up in [`Object::keys`] and `for...in`.
+    pub enumerable: bool,
+    /// Whether the property can be deleted or redefined.
+    pub configurable: bool,
+}
+
+impl PropertyAttributes {
+    /// Writable, enumerable and configurable, like a property created by
+    /// assignment.
+    pub fn all() -> Self {
+        Self {
+            writable: true,
+            enumerable: true,
+            configurable: true,
         }
     }
 }
@@ -600,83 +1486,191 @@ impl Function {
         self.function.clone()
     }
 
+    /// Creates a function backed by Rust. Returning `Err` throws the value as a
+    /// JavaScript exception. The closure is owned by the JavaScript function and
+    /// dropped when the function is garbage collected.
     #[allow(unused_variables)]
-    pub fn new(scope: &mut Scope, f: fn(&mut Scope, Args) -> Value) -> Self {
+    pub fn new<F>(scope: &mut Scope, f: F) -> Self
+    where
+        F: Fn(&mut Scope, Args) -> Result<Value, Value> + 'static,
+    {
         #[cfg(not(target_arch = "wasm32"))]
         {
+            let callback: Box<Callback> = Box::new(f);
+            let callback = Box::into_raw(Box::new(callback));
             let function = scope.enter(|scope| {
-                let f_ptr = v8::Number::new(scope, f as usize as f64);
+                let data = v8::External::new(scope, callback as *mut std::ffi::c_void);
                 let function = v8::Function::builder(
                     |v8_scope: &mut v8::HandleScope<'_>,
                      v8_args: v8::FunctionCallbackArguments<'_>,
                      mut v8_ret: v8::ReturnValue<'_>| {
-                        let f: fn(&mut Scope, Args) -> Value = unsafe {
-                            std::mem::transmute(
-                                v8_args.data().number_value(v8_scope).unwrap() as usize
-                            )
-                        };
+                        let data = v8::Local::<v8::External>::try_from(v8_args.data()).unwrap();
+                        let f = unsafe { &*(data.value() as *const Box<Callback>) };
                         let mut args = Args { args: vec![] };
                         for i in 0..v8_args.length() {
                             args.args.push(Value::from_v8(v8_scope, v8_args.get(i)));
                         }
-                        let value = {
+                        let result = {
                             let mut scope = Scope::scope(v8_scope);
                             f(&mut scope, args)
                         };
-                        v8_ret.set(value.to_v8(v8_scope));
+                        match result {
+                            Ok(value) => {
+                                // a string that's too long throws instead
+                                if let Some(value) = value.to_v8(v8_scope) {
+                                    v8_ret.set(value);
+                                }
+                            }
+                            // a terminated script is already unwinding
+                            Err(_) if v8_scope.is_execution_terminating() => {}
+                            Err(exception) => {
+                                if let Some(exception) = exception.to_v8(v8_scope) {
+                                    v8_scope.throw_exception(exception);
+                                }
+                            }
+                        }
                     },
                 )
-                .data(f_ptr.into())
+                .data(data.into())
                 .build(scope)
                 .unwrap();
+
+                // the weak handle is kept alive until its own finalizer runs, which
+                // frees both the closure and the handle
+                let weak_handle = std::rc::Rc::new(std::cell::Cell::new(None));
+                let weak = v8::Weak::with_finalizer(
+                    scope,
+                    function,
+                    Box::new({
+                        let weak_handle = weak_handle.clone();
+                        move |isolate: &mut v8::Isolate| unsafe {
+                            drop(Box::from_raw(callback));
+                            drop(v8::Weak::<v8::Function>::from_raw(isolate, weak_handle.take()));
+                        }
+                    }),
+                );
+                weak_handle.set(weak.into_raw());
+
                 v8::Global::new(scope, function)
             });
             Self { function }
         }
         #[cfg(target_arch = "wasm32")]
         {
-            use js_sys::{Array, Reflect};
+            use js_sys::Array;
             use wasm_bindgen::{closure::Closure, JsCast, JsValue};
-            use web_sys::window;
-            let f_ptr = f as usize;
-            let handle_str = format!("__fn_{}", f_ptr);
-            let handle = JsValue::from(&handle_str);
-            if Reflect::get(&window().into(), &JsValue::from(&handle))
-                .unwrap()
-                .is_undefined()
+            let closure = Closure::<dyn Fn(JsValue) -> Result<JsValue, JsValue>>::new(
+                move |js_args: JsValue| {
+                    let mut scope = Scope::new();
+                    let js_args_array: Array = js_args.into();
+                    let mut args = Args { args: vec![] };
+                    for i in 0..js_args_array.length() {
+                        args.args.push(Value::from_web(js_args_array.get(i)));
+                    }
+                    f(&mut scope, args)
+                        .map(|value| value.to_web())
+                        .map_err(|exception| exception.to_web())
+                },
+            );
+            let args_wrapper = js_sys::Function::new_with_args(
+                "f",
+                "return function() { return f(Array.from(arguments)); }",
+            );
+            // into_js_value hands the closure over to the JS garbage collector
+            let function = args_wrapper
+                .call1(&JsValue::null(), &closure.into_js_value())
+                .unwrap();
+            Function::from_web(function.unchecked_into())
+        }
+    }
+
+    /// Like [`Function::new`], but accepts a closure that mutates its captured
+    /// state. Calling the function again while it is already running throws.
+    pub fn new_mut<F>(scope: &mut Scope, f: F) -> Self
+    where
+        F: FnMut(&mut Scope, Args) -> Result<Value, Value> + 'static,
+    {
+        let f = std::cell::RefCell::new(f);
+        Self::new(scope, move |scope, args| {
+            if let Ok(mut f) = f.try_borrow_mut() {
+                f(scope, args)
+            } else {
+                Err(Value::error(scope, "function called recursively"))
+            }
+        })
+    }
+
+    /// Creates a function backed by an async Rust closure. Calling it returns a
+    /// promise that settles once the future completes. On native, the future is
+    /// polled while a [`Promise`] is being awaited.
+    #[allow(unused_variables)]
+    pub fn new_async<F, Fut>(scope: &mut Scope, f: F) -> Self
+    where
+        F: Fn(Args) -> Fut + 'static,
+        Fut: std::future::Future<Output = Result<Value, Value>> + 'static,
+    {
+        Self::new(scope, move |scope, args| {
+            let future = f(args);
+            #[cfg(not(target_arch = "wasm32"))]
             {
-                let closure =
-                    Closure::<dyn Fn(JsValue) -> JsValue>::new(move |js_args: JsValue| {
-                        let mut scope = Scope::new();
-                        let js_args_array: Array = js_args.into();
-                        let mut args = Args { args: vec![] };
-                        for i in 0..js_args_array.length() {
-                            args.args.push(Value::from_web(js_args_array.get(i)));
-                        }
-                        let ret = f(&mut scope, args);
-                        ret.to_web()
-                    });
-                Reflect::set(&window().into(), &handle, closure.as_ref().unchecked_ref()).unwrap();
-                closure.forget();
+                Ok(scope.enter(|scope| {
+                    let promise = crate::task::spawn(scope, future);
+                    Value::Promise(Promise::from_v8(scope, promise))
+                }))
             }
-            Function::from_web(js_sys::eval(&format!("function args_wrapper() {{ return window.{}.apply(null, [Array.from(arguments)]); }}; args_wrapper", &handle_str)).unwrap().try_into().unwrap())
-        }
+            #[cfg(target_arch = "wasm32")]
+            {
+                let promise = wasm_bindgen_futures::future_to_promise(async move {
+                    future
+                        .await
+                        .map(|value| value.to_web())
+                        .map_err(|exception| exception.to_web())
+                });
+                Ok(Value::Promise(Promise::from_web(promise)))
+            }
+        })
+    }
+
+    /// Like [`Function::call`], but terminates the call with
+    /// [`JsError::Terminated`] if it runs for longer than `timeout`. On the web,
+    /// scripts can't be interrupted, so the timeout is ignored.
+    pub fn call_with_timeout(
+        &self,
+        scope: &mut Scope,
+        args: &[Value],
+        timeout: std::time::Duration,
+    ) -> Result<Value, JsError> {
+        self.call_inner(scope, args, Some(timeout))
+    }
+
+    pub fn call(&self, scope: &mut Scope, args: &[Value]) -> Result<Value, JsError> {
+        self.call_inner(scope, args, None)
     }
 
     #[allow(unused_variables)]
-    pub fn call(&self, scope: &mut Scope, args: &[Value]) -> Value {
+    fn call_inner(
+        &self,
+        scope: &mut Scope,
+        args: &[Value],
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Value, JsError> {
         #[cfg(not(target_arch = "wasm32"))]
         {
             let function = self.function.clone();
-            scope.enter(move |scope| {
+            scope.try_enter(move |scope| {
                 let function = v8::Local::new(scope, function);
                 let recv = v8::null(scope);
                 let args = args
                     .iter()
                     .map(|value| value.to_v8(scope))
-                    .collect::<Vec<_>>();
-                let ret = function.call(scope, recv.into(), &args).unwrap();
-                Value::from_v8(scope, ret)
+                    .collect::<Option<Vec<_>>>()?;
+                let previous =
+                    timeout.map(|timeout| crate::termination::arm_watchdog(scope, timeout));
+                let ret = function.call(scope, recv.into(), &args);
+                if let Some(previous) = previous {
+                    crate::termination::disarm_watchdog(scope, previous);
+                }
+                Some(Value::from_v8(scope, ret?))
             })
         }
         #[cfg(target_arch = "wasm32")]
@@ -685,11 +1679,10 @@ impl Function {
             for arg in args {
                 array.push(&arg.to_web());
             }
-            let ret = self
-                .function
+            self.function
                 .apply(&wasm_bindgen::JsValue::null(), &array)
-                .unwrap();
-            Value::from_web(ret)
+                .map(Value::from_web)
+                .map_err(JsError::from_web)
         }
     }
 }
@@ -706,6 +1699,8 @@ impl From<Function> for Value {
     }
 }
 
+pub(crate) type Callback = dyn Fn(&mut Scope, Args) -> Result<Value, Value>;
+
 pub struct Args {
     pub(crate) args: Vec<Value>,
 }
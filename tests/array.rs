@@ -0,0 +1,89 @@
+use unijs::{Array, Module, Scope, Value};
+
+fn numbers(scope: &mut Scope, array: &Array) -> Vec<f64> {
+    array
+        .to_vec(scope)
+        .unwrap()
+        .into_iter()
+        .map(|value| value.into_number().unwrap())
+        .collect()
+}
+
+fn array(scope: &mut Scope, numbers: &[f64]) -> Array {
+    Array::from_iter(scope, numbers.iter().copied().map(Value::Number)).unwrap()
+}
+
+#[test]
+fn negative_indices_count_from_the_end() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+    let items = array(&mut scope, &[0.0, 1.0, 2.0, 3.0, 4.0]);
+
+    let slice = items.slice(&mut scope, -2, 5).unwrap();
+    assert_eq!(numbers(&mut scope, &slice), [3.0, 4.0]);
+    let slice = items.slice(&mut scope, 1, -1).unwrap();
+    assert_eq!(numbers(&mut scope, &slice), [1.0, 2.0, 3.0]);
+    let slice = items.slice(&mut scope, -100, 2).unwrap();
+    assert_eq!(numbers(&mut scope, &slice), [0.0, 1.0]);
+    let slice = items.slice(&mut scope, 3, 1).unwrap();
+    assert_eq!(slice.length(&mut scope), 0);
+
+    let removed = items
+        .splice(&mut scope, -2, 1, [Value::Number(10.0)])
+        .unwrap();
+    assert_eq!(numbers(&mut scope, &removed), [3.0]);
+    assert_eq!(numbers(&mut scope, &items), [0.0, 1.0, 2.0, 10.0, 4.0]);
+    let removed = items.splice(&mut scope, -100, 2, []).unwrap();
+    assert_eq!(numbers(&mut scope, &removed), [0.0, 1.0]);
+    assert_eq!(numbers(&mut scope, &items), [2.0, 10.0, 4.0]);
+}
+
+#[test]
+fn out_of_range_insert_and_remove() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+    let items = array(&mut scope, &[1.0, 2.0]);
+
+    // removing past the end leaves the array alone
+    assert!(items.remove(&mut scope, 2).unwrap().is_none());
+    assert!(items.remove(&mut scope, u32::MAX).unwrap().is_none());
+    assert_eq!(numbers(&mut scope, &items), [1.0, 2.0]);
+
+    // inserting past the end appends, without leaving holes
+    items.insert(&mut scope, 100, Value::Number(3.0)).unwrap();
+    items.insert(&mut scope, u32::MAX, Value::Number(4.0)).unwrap();
+    assert_eq!(numbers(&mut scope, &items), [1.0, 2.0, 3.0, 4.0]);
+
+    items.insert(&mut scope, 0, Value::Number(0.0)).unwrap();
+    assert_eq!(items.remove(&mut scope, 4).unwrap().unwrap().into_number(), Some(4.0));
+    assert_eq!(numbers(&mut scope, &items), [0.0, 1.0, 2.0, 3.0]);
+
+    let empty = array(&mut scope, &[]);
+    assert!(empty.pop(&mut scope).unwrap().is_none());
+    assert!(empty.remove(&mut scope, 0).unwrap().is_none());
+}
+
+#[test]
+fn replaced_methods_are_not_used() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(
+        r#"
+        exports.items = [1, 2, 3];
+        exports.items.splice = () => { throw new Error("replaced"); };
+        Array.prototype.splice = () => { throw new Error("replaced"); };
+        Array.prototype.slice = () => { throw new Error("replaced"); };
+        "#,
+    )
+    .unwrap();
+    let items = exports
+        .into_object()
+        .unwrap()
+        .get(&mut scope, "items")
+        .unwrap()
+        .into_array()
+        .unwrap();
+    items.insert(&mut scope, 0, Value::Number(0.0)).unwrap();
+    assert_eq!(items.pop(&mut scope).unwrap().unwrap().into_number(), Some(3.0));
+    let slice = items.slice(&mut scope, 1, 3).unwrap();
+    assert_eq!(numbers(&mut scope, &slice), [1.0, 2.0]);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use common::block_on;
+use unijs::{Function, Module, Value};
+
+/// Returns `Pending` once before finishing, like real I/O.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+const JS: &str = r#"
+    exports.settle = async function(f, arg) {
+        try {
+            return "resolved: " + await f(arg);
+        } catch (err) {
+            return "rejected: " + (err instanceof Error ? err.message : err);
+        }
+    }
+    exports.call = function(f, arg) {
+        return f(arg);
+    }
+"#;
+
+#[test]
+fn scripts_await_async_functions() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let settle = exports
+        .get(&mut scope, "settle")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let double = Function::new_async(&mut scope, |args| async move {
+        YieldOnce(false).await;
+        match args.get(0) {
+            Value::Number(number) => Ok(Value::Number(number * 2.0)),
+            _ => Err(Value::String("not a number".to_owned())),
+        }
+    });
+
+    for (arg, expected) in [
+        (Value::Number(21.0), "resolved: 42"),
+        (Value::Null, "rejected: not a number"),
+    ] {
+        let promise = settle
+            .call(&mut scope, &[double.clone().into(), arg])
+            .unwrap()
+            .into_promise()
+            .unwrap();
+        let result = block_on(promise.into_future(&mut scope)).unwrap();
+        assert_eq!(result.into_string().as_deref(), Some(expected));
+    }
+}
+
+#[test]
+fn rejections_reach_rust() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let call = exports
+        .get(&mut scope, "call")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let fail = Function::new_async(&mut scope, |_args| async move {
+        YieldOnce(false).await;
+        Err(Value::String("failed".to_owned()))
+    });
+
+    let promise = call
+        .call(&mut scope, &[fail.into(), Value::Undefined])
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    let error = block_on(promise.into_future(&mut scope)).unwrap_err();
+    assert_eq!(error.message(), "failed");
+}
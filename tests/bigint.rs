@@ -0,0 +1,66 @@
+use unijs::{BigInt, Module, Value};
+
+const JS: &str = r#"
+    exports.toString = function(value) {
+        return value.toString();
+    }
+    exports.parse = function(value) {
+        return BigInt(value);
+    }
+"#;
+
+#[test]
+fn i128_and_u128_edges_round_trip() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let to_string = exports.get(&mut scope, "toString").unwrap().into_function().unwrap();
+    let parse = exports.get(&mut scope, "parse").unwrap().into_function().unwrap();
+
+    let edges = [
+        (BigInt::from(0i128), "0"),
+        (BigInt::from(-1i128), "-1"),
+        (BigInt::from(i128::MIN), "-170141183460469231731687303715884105728"),
+        (BigInt::from(i128::MAX), "170141183460469231731687303715884105727"),
+        (BigInt::from(u128::MAX), "340282366920938463463374607431768211455"),
+    ];
+    for (bigint, expected) in edges {
+        assert_eq!(bigint.to_string(), expected);
+        let string = to_string.call(&mut scope, &[bigint.clone().into()]).unwrap();
+        assert_eq!(string.into_string().as_deref(), Some(expected));
+        let parsed = parse
+            .call(&mut scope, &[Value::String(expected.to_owned())])
+            .unwrap();
+        assert_eq!(parsed.into_bigint(), Some(bigint));
+    }
+}
+
+#[test]
+fn conversions_fail_out_of_range() {
+    let min = BigInt::from(i128::MIN);
+    assert_eq!(min.to_i128(), Some(i128::MIN));
+    assert_eq!(min.to_u128(), None);
+    assert_eq!(min.to_i64(), None);
+    let below_min = BigInt::from_words(true, vec![1, 1 << 63]);
+    assert_eq!(below_min.to_i128(), None);
+
+    let max = BigInt::from(u128::MAX);
+    assert_eq!(max.to_u128(), Some(u128::MAX));
+    assert_eq!(max.to_i128(), None);
+    let above_max = BigInt::from_words(false, vec![0, 0, 1]);
+    assert_eq!(above_max.to_u128(), None);
+    assert_eq!(above_max.to_string(), "340282366920938463463374607431768211456");
+
+    assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+    assert_eq!(BigInt::from(u64::MAX).to_i64(), None);
+    assert_eq!(BigInt::from(-1i64).to_u64(), None);
+}
+
+#[test]
+fn there_is_no_negative_zero() {
+    let zero = BigInt::from_words(true, vec![0, 0]);
+    assert!(!zero.is_negative());
+    assert!(zero.words().is_empty());
+    assert_eq!(zero, BigInt::from(0u64));
+    assert_eq!(BigInt::from_bytes_le(false, &[1, 0, 0]).to_bytes_le(), vec![1]);
+}
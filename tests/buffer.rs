@@ -0,0 +1,56 @@
+use unijs::{ArrayBuffer, Module, TypedArray, TypedArrayKind};
+
+#[test]
+fn views_share_their_buffer() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+    let buffer = ArrayBuffer::from_vec(&mut scope, (0..16).collect());
+    let view = TypedArray::new(&mut scope, TypedArrayKind::Uint32, &buffer, 4, 2).unwrap();
+    assert_eq!(view.byte_offset(&mut scope), 4);
+    assert_eq!(view.byte_length(&mut scope), 8);
+    assert_eq!(view.length(&mut scope), 2);
+    assert_eq!(view.to_vec(&mut scope), (4..12).collect::<Vec<u8>>());
+
+    view.with_bytes_mut(&mut scope, |bytes| bytes.fill(0xff));
+    let bytes = buffer.to_vec(&mut scope);
+    assert_eq!(&bytes[..4], &[0, 1, 2, 3]);
+    assert_eq!(&bytes[4..12], &[0xff; 8]);
+    assert_eq!(&bytes[12..], &[12, 13, 14, 15]);
+}
+
+#[test]
+fn misaligned_offsets_are_rejected() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+    let buffer = ArrayBuffer::new(&mut scope, 16);
+    for (kind, offset) in [
+        (TypedArrayKind::Uint16, 1),
+        (TypedArrayKind::Int32, 2),
+        (TypedArrayKind::Float64, 4),
+    ] {
+        let error = TypedArray::new(&mut scope, kind, &buffer, offset, 1).unwrap_err();
+        assert_eq!(error.name(), "RangeError");
+    }
+    // single byte elements can start anywhere
+    let view = TypedArray::new(&mut scope, TypedArrayKind::Int8, &buffer, 3, 1).unwrap();
+    assert_eq!(view.byte_offset(&mut scope), 3);
+}
+
+#[test]
+fn views_past_the_end_are_rejected() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+    let buffer = ArrayBuffer::new(&mut scope, 16);
+    for (kind, offset, length) in [
+        (TypedArrayKind::Uint8, 0, 17),
+        (TypedArrayKind::Uint8, 16, 1),
+        (TypedArrayKind::Float64, 8, 2),
+        (TypedArrayKind::Uint32, 0, usize::MAX),
+    ] {
+        let error = TypedArray::new(&mut scope, kind, &buffer, offset, length).unwrap_err();
+        assert_eq!(error.name(), "RangeError");
+    }
+    // a view may end exactly at the end of the buffer
+    let view = TypedArray::new(&mut scope, TypedArrayKind::Float64, &buffer, 8, 1).unwrap();
+    assert_eq!(view.byte_length(&mut scope), 8);
+}
@@ -0,0 +1,78 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use unijs::{Map, Module, Set, Value};
+
+const JS: &str = r#"
+    exports.describe = function(map) {
+        return [...map].map(([key, value]) => `${typeof key} ${key}=${value}`).join(", ");
+    }
+    exports.size = function(collection) {
+        return collection.size;
+    }
+    exports.mixed = new Map([[1, "one"], ["two", 2]]);
+"#;
+
+#[test]
+fn from_entries_keeps_key_types_and_order() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let describe = exports.get(&mut scope, "describe").unwrap().into_function().unwrap();
+
+    let entries = BTreeMap::from([(3u32, "c"), (1, "a"), (2, "b")]);
+    let map = Map::from_entries(&mut scope, &entries).unwrap();
+    assert_eq!(map.len(&mut scope), 3);
+    let description = describe.call(&mut scope, &[map.clone().into()]).unwrap();
+    assert_eq!(
+        description.into_string().as_deref(),
+        Some("number 1=a, number 2=b, number 3=c")
+    );
+
+    let value = map.get(&mut scope, Value::Number(2.0)).unwrap();
+    assert_eq!(value.into_string().as_deref(), Some("b"));
+    // keys are compared by value and type, so "2" isn't 2
+    let value = map.get(&mut scope, Value::String("2".to_owned())).unwrap();
+    assert!(value.is_undefined());
+
+    let collected: HashMap<u32, String> = map.collect(&mut scope).unwrap();
+    assert_eq!(collected.len(), 3);
+    assert_eq!(collected[&3], "c");
+}
+
+#[test]
+fn from_values_removes_duplicates() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let size = exports.get(&mut scope, "size").unwrap().into_function().unwrap();
+
+    let set = Set::from_values(&mut scope, &["red", "green", "red"]).unwrap();
+    assert_eq!(set.len(&mut scope), 2);
+    let length = size.call(&mut scope, &[set.clone().into()]).unwrap();
+    assert_eq!(length.into_number(), Some(2.0));
+    let has = set.has(&mut scope, Value::String("green".to_owned())).unwrap();
+    assert!(has);
+
+    let collected: BTreeSet<String> = set.collect(&mut scope).unwrap();
+    assert_eq!(
+        collected.into_iter().collect::<Vec<_>>(),
+        vec!["green".to_owned(), "red".to_owned()]
+    );
+
+    let empty = Set::from_values::<u32, _>(&mut scope, &[]).unwrap();
+    assert!(empty.is_empty(&mut scope));
+}
+
+#[test]
+fn collect_fails_on_mismatched_entries() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let mixed = exports.get(&mut scope, "mixed").unwrap().into_map().unwrap();
+
+    assert!(mixed.collect::<u32, String, HashMap<_, _>>(&mut scope).is_err());
+    let entries: Vec<(Value, Value)> = mixed.entries(&mut scope).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].0.clone().into_number(), Some(1.0));
+    assert_eq!(entries[1].0.clone().into_string().as_deref(), Some("two"));
+}
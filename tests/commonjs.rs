@@ -0,0 +1,92 @@
+use unijs::{MemoryModuleLoader, Runtime};
+
+#[test]
+fn circular_requires_see_partial_exports() {
+    unijs::init();
+    let loader = MemoryModuleLoader::new()
+        .with(
+            "a.js",
+            r#"
+            exports.loaded = false;
+            const b = require("./b.js");
+            exports.loaded = true;
+            exports.bSawA = b.sawA;
+            "#,
+        )
+        .with(
+            "b.js",
+            r#"
+            const a = require("./a.js");
+            exports.sawA = a.loaded;
+            "#,
+        );
+    let mut runtime = Runtime::new();
+    runtime.set_module_loader(loader);
+    let module = runtime
+        .load(
+            r#"
+            const a = require("./a.js");
+            // required modules are cached, so this is the same object
+            module.exports = { a, same: a === require("./a.js") };
+            "#,
+        )
+        .unwrap();
+    let exports = module.exports().clone().into_object().unwrap();
+    let mut scope = runtime.scope(module.context());
+    let same = exports.get(&mut scope, "same").unwrap();
+    assert_eq!(same.into_bool(), Some(true));
+    let a = exports.get(&mut scope, "a").unwrap().into_object().unwrap();
+    assert_eq!(a.get(&mut scope, "loaded").unwrap().into_bool(), Some(true));
+    // b ran while a was still loading
+    assert_eq!(a.get(&mut scope, "bSawA").unwrap().into_bool(), Some(false));
+}
+
+#[test]
+fn failed_modules_are_not_cached() {
+    unijs::init();
+    let loader = MemoryModuleLoader::new().with(
+        "broken.js",
+        r#"
+        exports.partial = true;
+        throw new Error("broken");
+        "#,
+    );
+    let mut runtime = Runtime::new();
+    runtime.set_module_loader(loader);
+    let module = runtime
+        .load(
+            r#"
+            module.exports = function() {
+                try {
+                    require("./broken.js");
+                    return "loaded";
+                } catch (err) {
+                    return err.message;
+                }
+            };
+            "#,
+        )
+        .unwrap();
+    let load = module.exports().clone().into_function().unwrap();
+    let mut scope = runtime.scope(module.context());
+    for _ in 0..2 {
+        let result = load.call(&mut scope, &[]).unwrap();
+        assert_eq!(result.into_string().as_deref(), Some("broken"));
+    }
+}
+
+#[test]
+fn filename_and_dirname() {
+    unijs::init();
+    let loader = MemoryModuleLoader::new().with(
+        "lib/path.js",
+        "module.exports = __dirname + ' ' + __filename;",
+    );
+    let mut runtime = Runtime::new();
+    runtime.set_module_loader(loader);
+    let module = runtime
+        .load(r#"module.exports = require("./lib/path.js");"#)
+        .unwrap();
+    let path = module.exports().clone().into_string();
+    assert_eq!(path.as_deref(), Some("lib lib/path.js"));
+}
@@ -0,0 +1,63 @@
+use std::{cell::RefCell, rc::Rc};
+
+use unijs::{ConsoleLevel, ConsoleSink, Runtime};
+
+#[derive(Default, Clone)]
+struct Collect(Rc<RefCell<Vec<(ConsoleLevel, String)>>>);
+
+impl ConsoleSink for Collect {
+    fn write(&self, level: ConsoleLevel, message: &str) {
+        self.0.borrow_mut().push((level, message.to_owned()));
+    }
+}
+
+fn run(js: &str) -> Vec<(ConsoleLevel, String)> {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let collect = Collect::default();
+    runtime.set_console_sink(collect.clone());
+    runtime.load(js).unwrap();
+    let messages = collect.0.borrow().clone();
+    messages
+}
+
+#[test]
+fn levels_match_methods() {
+    let messages = run(r#"
+        console.log("log");
+        console.info("info");
+        console.debug("debug");
+        console.warn("warn");
+        console.error("error");
+    "#);
+    assert_eq!(
+        messages,
+        [
+            (ConsoleLevel::Info, "log".to_owned()),
+            (ConsoleLevel::Info, "info".to_owned()),
+            (ConsoleLevel::Debug, "debug".to_owned()),
+            (ConsoleLevel::Warn, "warn".to_owned()),
+            (ConsoleLevel::Error, "error".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn formats_substitutions() {
+    let messages = run(r#"console.log("%s is %d%%", "answer", 42.5, [1, "two"]);"#);
+    assert_eq!(messages[0].1, "answer is 42% [ 1, 'two' ]");
+}
+
+#[test]
+fn accessors_are_not_invoked() {
+    let messages = run(r#"
+        console.log({
+            a: 1,
+            get b() { throw new Error("getter ran"); },
+            set c(value) {},
+            get d() { return 1; },
+            set d(value) {},
+        });
+    "#);
+    assert_eq!(messages[0].1, "{ a: 1, b: [Getter], c: [Setter], d: [Getter/Setter] }");
+}
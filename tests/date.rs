@@ -0,0 +1,61 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use unijs::{Date, Module, Value};
+
+#[test]
+fn iso_strings_match_javascript() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(
+        "exports.iso = function(timestamp) { return new Date(timestamp).toISOString(); }",
+    )
+    .unwrap();
+    let exports = exports.into_object().unwrap();
+    let iso = exports.get(&mut scope, "iso").unwrap().into_function().unwrap();
+
+    for (timestamp, expected) in [
+        (0.0, "1970-01-01T00:00:00.000Z"),
+        (-1.0, "1969-12-31T23:59:59.999Z"),
+        (951_782_400_000.0, "2000-02-29T00:00:00.000Z"),
+        (-62_167_219_200_000.0, "0000-01-01T00:00:00.000Z"),
+        (-62_167_219_200_001.0, "-000001-12-31T23:59:59.999Z"),
+        (-62_198_755_200_000.0, "-000001-01-01T00:00:00.000Z"),
+        (253_402_300_799_999.0, "9999-12-31T23:59:59.999Z"),
+        (253_402_300_800_000.0, "+010000-01-01T00:00:00.000Z"),
+        (8.64e15, "+275760-09-13T00:00:00.000Z"),
+        (-8.64e15, "-271821-04-20T00:00:00.000Z"),
+    ] {
+        let date = Date::new(&mut scope, timestamp).unwrap();
+        assert_eq!(date.to_iso_string(&mut scope).as_deref(), Some(expected));
+        let string = iso.call(&mut scope, &[Value::Number(timestamp)]).unwrap();
+        assert_eq!(string.into_string().as_deref(), Some(expected));
+    }
+}
+
+#[test]
+fn invalid_dates() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+    for timestamp in [f64::NAN, 8.64e15 + 1.0, -8.64e15 - 1.0] {
+        let date = Date::new(&mut scope, timestamp).unwrap();
+        assert!(!date.is_valid(&mut scope));
+        assert_eq!(date.to_iso_string(&mut scope), None);
+        assert_eq!(date.to_system_time(&mut scope), None);
+    }
+}
+
+#[test]
+fn system_times_before_the_epoch_round_down() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+    let time = UNIX_EPOCH - Duration::from_micros(1500);
+    let date = Date::from_system_time(&mut scope, time).unwrap();
+    assert_eq!(date.timestamp(&mut scope), -2.0);
+    assert_eq!(
+        date.to_system_time(&mut scope),
+        Some(UNIX_EPOCH - Duration::from_millis(2))
+    );
+
+    let time = UNIX_EPOCH + Duration::from_micros(1500);
+    let date = Date::from_system_time(&mut scope, time).unwrap();
+    assert_eq!(date.timestamp(&mut scope), 1.0);
+}
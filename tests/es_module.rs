@@ -0,0 +1,64 @@
+mod common;
+
+use common::block_on;
+use unijs::{MemoryModuleLoader, Runtime};
+
+#[test]
+fn import_waits_for_top_level_await() {
+    unijs::init();
+    let loader = MemoryModuleLoader::new().with(
+        "main.js",
+        r#"
+        export let ready = false;
+        await new Promise(resolve => setTimeout(resolve, 10));
+        ready = true;
+        "#,
+    );
+    let mut runtime = Runtime::new();
+    runtime.set_module_loader(loader);
+    let module = block_on(runtime.import("./main.js")).unwrap();
+    let exports = module.exports().clone().into_object().unwrap();
+    let mut scope = runtime.scope(module.context());
+    let ready = exports.get(&mut scope, "ready").unwrap();
+    assert_eq!(ready.into_bool(), Some(true));
+}
+
+#[test]
+fn import_fails_when_top_level_await_rejects() {
+    unijs::init();
+    let loader = MemoryModuleLoader::new().with(
+        "main.js",
+        r#"
+        await new Promise((_, reject) => setTimeout(() => reject(new Error("late")), 10));
+        "#,
+    );
+    let mut runtime = Runtime::new();
+    runtime.set_module_loader(loader);
+    let error = block_on(runtime.import("./main.js")).unwrap_err();
+    assert_eq!(error.message(), "late");
+}
+
+#[test]
+fn relative_imports_resolve_against_their_own_module() {
+    unijs::init();
+    let loader = MemoryModuleLoader::new()
+        .with("a/util.js", "export const name = 'a';")
+        .with("b/util.js", "export const name = 'b';")
+        .with("a/index.js", "export { name } from './util.js';")
+        .with("b/index.js", "export { name } from './util.js';")
+        .with(
+            "main.js",
+            r#"
+            import { name as a } from "./a/index.js";
+            import { name as b } from "./b/index.js";
+            export const names = a + b;
+            "#,
+        );
+    let mut runtime = Runtime::new();
+    runtime.set_module_loader(loader);
+    let module = block_on(runtime.import("./main.js")).unwrap();
+    let exports = module.exports().clone().into_object().unwrap();
+    let mut scope = runtime.scope(module.context());
+    let names = exports.get(&mut scope, "names").unwrap();
+    assert_eq!(names.into_string().as_deref(), Some("ab"));
+}
@@ -0,0 +1,126 @@
+use std::{cell::Cell, rc::Rc};
+
+use unijs::{Args, Function, Module, Scope, Value};
+
+const JS: &str = r#"
+    exports.check = function(f, arg) {
+        try {
+            return "ok: " + f(arg);
+        } catch (err) {
+            return "caught: " + (err instanceof Error ? err.message : err);
+        }
+    }
+    exports.call = function(f, arg) {
+        return f(arg);
+    }
+"#;
+
+fn positive(scope: &mut Scope, args: Args) -> Result<Value, Value> {
+    match args.get(0) {
+        Value::Number(number) if number > 0.0 => Ok(Value::Number(number)),
+        _ => Err(Value::error(scope, "not positive")),
+    }
+}
+
+fn throw_string(_scope: &mut Scope, _args: Args) -> Result<Value, Value> {
+    Err(Value::String("plain".to_owned()))
+}
+
+#[test]
+fn err_is_thrown_to_scripts() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let check = exports
+        .get(&mut scope, "check")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let positive = Function::new(&mut scope, positive);
+
+    let ok = check
+        .call(&mut scope, &[positive.clone().into(), Value::Number(2.0)])
+        .unwrap();
+    assert_eq!(ok.into_string().as_deref(), Some("ok: 2"));
+    let caught = check
+        .call(&mut scope, &[positive.into(), Value::Number(-2.0)])
+        .unwrap();
+    assert_eq!(caught.into_string().as_deref(), Some("caught: not positive"));
+
+    // any value can be thrown, not just errors
+    let throw_string = Function::new(&mut scope, throw_string);
+    let caught = check
+        .call(&mut scope, &[throw_string.into(), Value::Undefined])
+        .unwrap();
+    assert_eq!(caught.into_string().as_deref(), Some("caught: plain"));
+}
+
+#[test]
+fn uncaught_err_reaches_the_caller() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let call = exports
+        .get(&mut scope, "call")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let positive = Function::new(&mut scope, positive);
+
+    let error = call
+        .call(&mut scope, &[positive.clone().into(), Value::Number(-1.0)])
+        .unwrap_err();
+    assert_eq!(error.name(), "Error");
+    assert_eq!(error.message(), "not positive");
+
+    // calling the function directly from Rust fails the same way
+    let error = positive.call(&mut scope, &[Value::Null]).unwrap_err();
+    assert_eq!(error.message(), "not positive");
+}
+
+#[test]
+fn closures_keep_their_captured_state() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let call = exports
+        .get(&mut scope, "call")
+        .unwrap()
+        .into_function()
+        .unwrap();
+
+    let calls = Rc::new(Cell::new(0));
+    let counted = Function::new(&mut scope, {
+        let calls = calls.clone();
+        move |_scope, args| {
+            calls.set(calls.get() + 1);
+            Ok(args.get(0))
+        }
+    });
+    for _ in 0..3 {
+        call.call(&mut scope, &[counted.clone().into(), Value::Null])
+            .unwrap();
+    }
+    assert_eq!(calls.get(), 3);
+
+    // closures of the same type are still separate functions
+    let greetings: Vec<Function> = ["hello", "goodbye"]
+        .into_iter()
+        .map(|greeting| {
+            let greeting = greeting.to_owned();
+            Function::new(&mut scope, move |_scope, args| {
+                let name = args.get(0).into_string().unwrap_or_default();
+                Ok(Value::String(format!("{greeting}, {name}")))
+            })
+        })
+        .collect();
+    let name = Value::String("world".to_owned());
+    let hello = call
+        .call(&mut scope, &[greetings[0].clone().into(), name.clone()])
+        .unwrap();
+    assert_eq!(hello.into_string().as_deref(), Some("hello, world"));
+    let goodbye = call
+        .call(&mut scope, &[greetings[1].clone().into(), name])
+        .unwrap();
+    assert_eq!(goodbye.into_string().as_deref(), Some("goodbye, world"));
+}
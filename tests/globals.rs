@@ -0,0 +1,75 @@
+use unijs::{Module, Object, Value};
+
+fn load() -> (unijs::Scope<'static, 'static>, Object, Value) {
+    unijs::init();
+    let js = r#"
+        exports.global = globalThis;
+        exports.read = function() {
+            return typeof answer === "undefined" ? "missing" : answer;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let global = exports.get(&mut scope, "global").unwrap().into_object().unwrap();
+    let read = exports.get(&mut scope, "read").unwrap();
+    (scope, global, read)
+}
+
+#[test]
+fn global_set_from_rust_is_visible_to_scripts() {
+    let (mut scope, global, read) = load();
+    let read = read.into_function().unwrap();
+    global.set(&mut scope, "answer", Value::Number(42.0)).unwrap();
+    let value = read.call(&mut scope, &[]).unwrap();
+    assert_eq!(value.into_number(), Some(42.0));
+}
+
+#[test]
+fn global_can_be_overwritten() {
+    let (mut scope, global, read) = load();
+    let read = read.into_function().unwrap();
+    global.set(&mut scope, "answer", Value::Number(1.0)).unwrap();
+    assert_eq!(read.call(&mut scope, &[]).unwrap().into_number(), Some(1.0));
+    global
+        .set(&mut scope, "answer", Value::String("two".into()))
+        .unwrap();
+    let value = read.call(&mut scope, &[]).unwrap();
+    assert_eq!(value.into_string().as_deref(), Some("two"));
+}
+
+#[test]
+fn global_can_be_deleted() {
+    let (mut scope, global, read) = load();
+    let read = read.into_function().unwrap();
+    global.set(&mut scope, "answer", Value::Number(42.0)).unwrap();
+    assert!(global.delete(&mut scope, "answer").unwrap());
+    let value = read.call(&mut scope, &[]).unwrap();
+    assert_eq!(value.into_string().as_deref(), Some("missing"));
+}
+
+#[test]
+fn global_set_by_a_script_survives_between_calls() {
+    unijs::init();
+    let js = r#"
+        globalThis.counter = 0;
+        exports.increment = function() {
+            return ++globalThis.counter;
+        }
+    "#;
+    let (mut scope, exports) = Module::load(js).unwrap();
+    let exports = exports.into_object().unwrap();
+    let increment = exports
+        .get(&mut scope, "increment")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    for expected in 1..=3 {
+        let value = increment.call(&mut scope, &[]).unwrap();
+        assert_eq!(value.into_number(), Some(expected as f64));
+    }
+    // creating values in between doesn't switch to another context
+    Object::new(&mut scope);
+    Value::from_json(&mut scope, serde_json::json!({ "counter": 10 })).unwrap();
+    let value = increment.call(&mut scope, &[]).unwrap();
+    assert_eq!(value.into_number(), Some(4.0));
+}
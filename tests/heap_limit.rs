@@ -0,0 +1,45 @@
+use unijs::{JsError, Runtime, Value};
+
+const LEAK: &str = r#"
+    const hoard = [];
+    for (;;) {
+        hoard.push(new Array(1024).fill("leak"));
+    }
+"#;
+
+#[test]
+fn scripts_past_the_heap_limit_fail_with_out_of_memory() {
+    unijs::init();
+    let mut runtime = Runtime::with_heap_limits(0, 16 * 1024 * 1024);
+    assert!(matches!(runtime.load(LEAK), Err(JsError::OutOfMemory)));
+
+    // the runtime stays usable, and fails the same way again
+    let module = runtime.load("exports.answer = 42;").unwrap();
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let answer = exports.get(&mut scope, "answer").unwrap();
+    assert_eq!(answer.into_number(), Some(42.0));
+    drop(scope);
+    assert!(matches!(runtime.load(LEAK), Err(JsError::OutOfMemory)));
+}
+
+#[test]
+fn functions_past_the_heap_limit_fail_with_out_of_memory() {
+    unijs::init();
+    let mut runtime = Runtime::with_heap_limits(0, 16 * 1024 * 1024);
+    let module = runtime
+        .load(&format!(
+            "exports.leak = function() {{ {LEAK} }}; exports.add = (a, b) => a + b;"
+        ))
+        .unwrap();
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let leak = exports.get(&mut scope, "leak").unwrap().into_function().unwrap();
+    let add = exports.get(&mut scope, "add").unwrap().into_function().unwrap();
+
+    assert!(matches!(leak.call(&mut scope, &[]), Err(JsError::OutOfMemory)));
+    let sum = add
+        .call(&mut scope, &[Value::Number(1.0), Value::Number(2.0)])
+        .unwrap();
+    assert_eq!(sum.into_number(), Some(3.0));
+}
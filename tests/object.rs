@@ -0,0 +1,93 @@
+use unijs::{Module, Object, PropertyAttributes, Scope, Value};
+
+fn object(scope: &mut Scope, exports: &Object, name: &str) -> Object {
+    exports.get(scope, name).unwrap().into_object().unwrap()
+}
+
+#[test]
+fn integrity_levels() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(
+        r#"
+        exports.frozen = {};
+        exports.sealed = { a: 1 };
+        exports.closed = {};
+        "#,
+    )
+    .unwrap();
+    let exports = exports.into_object().unwrap();
+
+    let frozen = object(&mut scope, &exports, "frozen");
+    assert!(!frozen.is_frozen(&mut scope).unwrap());
+    frozen.freeze(&mut scope).unwrap();
+    assert!(frozen.is_frozen(&mut scope).unwrap());
+    assert!(frozen.is_sealed(&mut scope).unwrap());
+    assert!(!frozen.is_extensible(&mut scope).unwrap());
+
+    let sealed = object(&mut scope, &exports, "sealed");
+    sealed.seal(&mut scope).unwrap();
+    assert!(sealed.is_sealed(&mut scope).unwrap());
+    assert!(!sealed.is_frozen(&mut scope).unwrap());
+    sealed.set(&mut scope, "a", Value::Number(2.0)).unwrap();
+    assert_eq!(sealed.get(&mut scope, "a").unwrap().into_number(), Some(2.0));
+    assert!(!sealed.delete(&mut scope, "a").unwrap());
+
+    let closed = object(&mut scope, &exports, "closed");
+    assert!(closed.is_extensible(&mut scope).unwrap());
+    closed.prevent_extensions(&mut scope).unwrap();
+    assert!(!closed.is_extensible(&mut scope).unwrap());
+    // an empty object that can't be extended is also frozen
+    assert!(closed.is_frozen(&mut scope).unwrap());
+    let defined = closed
+        .define_property(&mut scope, "a", Value::Null, PropertyAttributes::all())
+        .unwrap();
+    assert!(!defined);
+}
+
+#[test]
+fn replaced_builtins_are_not_used() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(
+        r#"
+        exports.object = { a: 1 };
+        Object.freeze = () => { throw new Error("replaced"); };
+        Object.isFrozen = () => { throw new Error("replaced"); };
+        Object.isExtensible = () => { throw new Error("replaced"); };
+        globalThis.Object = null;
+        "#,
+    )
+    .unwrap();
+    let exports = exports.into_object().unwrap();
+    let object = object(&mut scope, &exports, "object");
+    object.freeze(&mut scope).unwrap();
+    assert!(object.is_frozen(&mut scope).unwrap());
+    assert!(!object.is_extensible(&mut scope).unwrap());
+    assert!(object.has_own(&mut scope, "a").unwrap());
+    assert_eq!(object.entries(&mut scope).unwrap().len(), 1);
+}
+
+#[test]
+fn objects_without_a_prototype() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(
+        r#"
+        exports.bare = Object.create(null);
+        exports.bare.a = 1;
+        Object.defineProperty(exports.bare, "hidden", { value: 2, enumerable: false });
+        exports.bare[Symbol("tag")] = 3;
+        "#,
+    )
+    .unwrap();
+    let exports = exports.into_object().unwrap();
+    let bare = object(&mut scope, &exports, "bare");
+
+    let keys = bare.property_keys(&mut scope).unwrap();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].clone().into_string().as_deref(), Some("a"));
+    assert!(keys[1].is_symbol());
+    assert_eq!(bare.keys(&mut scope).unwrap(), vec!["a".to_owned()]);
+    assert!(bare.has_own(&mut scope, "hidden").unwrap());
+    assert!(!bare.has(&mut scope, "toString").unwrap());
+    assert!(bare.delete(&mut scope, "a").unwrap());
+    assert!(bare.keys(&mut scope).unwrap().is_empty());
+}
@@ -0,0 +1,97 @@
+use unijs::{Module, Runtime, Scope, Value};
+
+fn export(scope: &mut Scope, module: &Module, name: &str) -> Value {
+    let exports = module.exports().clone().into_object().unwrap();
+    exports.get(scope, name).unwrap()
+}
+
+#[test]
+fn modules_share_values() {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let store = runtime
+        .load(
+            r#"
+            exports.create = function() {
+                return { items: [] };
+            }
+            exports.count = function(store) {
+                return store.items.length;
+            }
+            "#,
+        )
+        .unwrap();
+    let cart = runtime
+        .load(
+            r#"
+            exports.add = function(store, item) {
+                store.items.push(item);
+            }
+            "#,
+        )
+        .unwrap();
+
+    let mut scope = runtime.scope(store.context());
+    let create = export(&mut scope, &store, "create");
+    let count = export(&mut scope, &store, "count");
+    let add = export(&mut scope, &cart, "add");
+    let shop = create.into_function().unwrap().call(&mut scope, &[]).unwrap();
+    let add = add.into_function().unwrap();
+    for item in ["apple", "pear"] {
+        add.call(&mut scope, &[shop.clone(), Value::String(item.to_owned())])
+            .unwrap();
+    }
+    let count = count.into_function().unwrap().call(&mut scope, &[shop]);
+    assert_eq!(count.unwrap().into_number(), Some(2.0));
+}
+
+#[test]
+fn modules_have_their_own_globals() {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let first = runtime
+        .load(
+            r#"
+            globalThis.name = "first";
+            exports.name = function() {
+                return typeof name === "undefined" ? "missing" : name;
+            }
+            "#,
+        )
+        .unwrap();
+    let second = runtime
+        .load(
+            r#"
+            exports.name = function() {
+                return typeof name === "undefined" ? "missing" : name;
+            }
+            "#,
+        )
+        .unwrap();
+
+    let mut scope = runtime.scope(first.context());
+    for (module, expected) in [(&first, "first"), (&second, "missing")] {
+        let name = export(&mut scope, module, "name");
+        let name = name.into_function().unwrap().call(&mut scope, &[]).unwrap();
+        assert_eq!(name.into_string().as_deref(), Some(expected));
+    }
+}
+
+#[test]
+fn runtimes_on_one_thread_are_separate() {
+    unijs::init();
+    let mut first = Runtime::new();
+    let mut second = Runtime::new();
+    let a = first.load("exports.value = 1;").unwrap();
+    let b = second.load("exports.value = 2;").unwrap();
+    let error = first.load("throw new Error('failed');").unwrap_err();
+    assert_eq!(error.message(), "failed");
+
+    let mut scope = second.scope(b.context());
+    let value = export(&mut scope, &b, "value");
+    assert_eq!(value.into_number(), Some(2.0));
+    drop(scope);
+    let mut scope = first.scope(a.context());
+    let value = export(&mut scope, &a, "value");
+    assert_eq!(value.into_number(), Some(1.0));
+}
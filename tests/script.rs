@@ -0,0 +1,59 @@
+use unijs::{Runtime, Script};
+
+const SOURCE: &str = "globalThis.runs = (globalThis.runs || 0) + 1; runs;";
+
+fn code_cache() -> Vec<u8> {
+    let mut runtime = Runtime::new();
+    let context = runtime.context();
+    let mut scope = runtime.scope(&context);
+    let script = Script::compile(&mut scope, "runs.js", SOURCE).unwrap();
+    script.run(&mut scope).unwrap();
+    script.code_cache(&mut scope).unwrap()
+}
+
+#[test]
+fn scripts_run_in_any_context() {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let first = runtime.context();
+    let second = runtime.context();
+    let mut scope = runtime.scope(&first);
+    let script = Script::compile(&mut scope, "runs.js", SOURCE).unwrap();
+    script.run(&mut scope).unwrap();
+    assert_eq!(script.run(&mut scope).unwrap().into_number(), Some(2.0));
+    drop(scope);
+    let mut scope = runtime.scope(&second);
+    assert_eq!(script.run(&mut scope).unwrap().into_number(), Some(1.0));
+}
+
+#[test]
+fn matching_code_caches_are_accepted() {
+    unijs::init();
+    let cache = code_cache();
+    let mut runtime = Runtime::new();
+    let context = runtime.context();
+    let mut scope = runtime.scope(&context);
+    let script = Script::compile_with_cache(&mut scope, "runs.js", SOURCE, &cache).unwrap();
+    assert!(!script.cache_rejected());
+    assert_eq!(script.run(&mut scope).unwrap().into_number(), Some(1.0));
+}
+
+#[test]
+fn mismatched_code_caches_are_rejected() {
+    unijs::init();
+    let cache = code_cache();
+    let mut runtime = Runtime::new();
+    let context = runtime.context();
+    let mut scope = runtime.scope(&context);
+
+    // the cache was made for different source, so it's compiled from scratch
+    let other = "40 + 2;";
+    let script = Script::compile_with_cache(&mut scope, "other.js", other, &cache).unwrap();
+    assert!(script.cache_rejected());
+    assert_eq!(script.run(&mut scope).unwrap().into_number(), Some(42.0));
+
+    let garbage = b"not a code cache";
+    let script = Script::compile_with_cache(&mut scope, "runs.js", SOURCE, garbage).unwrap();
+    assert!(script.cache_rejected());
+    assert_eq!(script.run(&mut scope).unwrap().into_number(), Some(1.0));
+}
@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use unijs::{Module, Scope, SerializeOptions, Value};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Point(i32, i32),
+    Rect { width: u32, height: u32 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    name: Option<String>,
+    retries: Option<u8>,
+    nested: Option<Option<bool>>,
+}
+
+/// Serialized with `serialize_bytes` and deserialized with
+/// `deserialize_byte_buf`, like `serde_bytes`.
+#[derive(Debug, PartialEq)]
+struct Bytes(Vec<u8>);
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+            type Value = Bytes;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("bytes")
+            }
+
+            fn visit_byte_buf<E>(self, bytes: Vec<u8>) -> Result<Bytes, E> {
+                Ok(Bytes(bytes))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Bytes, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut bytes = vec![];
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(Bytes(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+fn scope() -> Scope<'static, 'static> {
+    unijs::init();
+    Module::load("").unwrap().0
+}
+
+fn round_trip<T>(scope: &mut Scope, value: &T, options: &SerializeOptions) -> T
+where
+    T: Serialize + DeserializeOwned,
+{
+    let value = Value::serialize_with_options(scope, value, options).unwrap();
+    value.deserialize(scope).unwrap()
+}
+
+#[test]
+fn enums_round_trip() {
+    let mut scope = scope();
+    let options = SerializeOptions::default();
+    let shapes = vec![
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Point(-1, 2),
+        Shape::Rect {
+            width: 3,
+            height: 4,
+        },
+    ];
+    assert_eq!(round_trip(&mut scope, &shapes, &options), shapes);
+}
+
+#[test]
+fn options_round_trip() {
+    let mut scope = scope();
+    let options = SerializeOptions::default();
+    let settings = Settings {
+        name: Some("app".to_owned()),
+        retries: None,
+        nested: Some(Some(true)),
+    };
+    assert_eq!(round_trip(&mut scope, &settings, &options), settings);
+    let settings = Settings {
+        name: None,
+        retries: Some(3),
+        nested: None,
+    };
+    assert_eq!(round_trip(&mut scope, &settings, &options), settings);
+}
+
+#[test]
+fn bytes_round_trip() {
+    let mut scope = scope();
+    let options = SerializeOptions::default();
+    let bytes = Bytes(vec![0, 1, 127, 128, 255]);
+    assert_eq!(round_trip(&mut scope, &bytes, &options), bytes);
+    assert_eq!(round_trip(&mut scope, &Bytes(vec![]), &options), Bytes(vec![]));
+}
+
+#[test]
+fn typed_arrays_become_bytes() {
+    unijs::init();
+    let (mut scope, exports) =
+        Module::load("exports.bytes = new Uint8Array([1, 2, 255]);").unwrap();
+    let exports = exports.into_object().unwrap();
+    let bytes: Bytes = exports
+        .get(&mut scope, "bytes")
+        .unwrap()
+        .deserialize(&mut scope)
+        .unwrap();
+    assert_eq!(bytes, Bytes(vec![1, 2, 255]));
+}
+
+#[test]
+fn large_integers_round_trip_as_bigint() {
+    let mut scope = scope();
+    let options = SerializeOptions {
+        large_integers_as_bigint: true,
+        ..Default::default()
+    };
+    assert_eq!(round_trip(&mut scope, &u64::MAX, &options), u64::MAX);
+    assert_eq!(round_trip(&mut scope, &i64::MIN, &options), i64::MIN);
+    assert_eq!(round_trip(&mut scope, &u128::MAX, &options), u128::MAX);
+    assert_eq!(round_trip(&mut scope, &i128::MIN, &options), i128::MIN);
+    let value = Value::serialize_with_options(&mut scope, &u64::MAX, &options).unwrap();
+    assert!(matches!(value, Value::BigInt(_)));
+    // safe integers stay numbers
+    let value = Value::serialize_with_options(&mut scope, &42u64, &options).unwrap();
+    assert!(matches!(value, Value::Number(_)));
+}
+
+#[test]
+fn numbers_out_of_range_are_not_integers() {
+    unijs::init();
+    let (mut scope, exports) = Module::load("exports.big = 2 ** 64;").unwrap();
+    let exports = exports.into_object().unwrap();
+    let big = exports.get(&mut scope, "big").unwrap();
+    assert!(big.clone().deserialize::<u64>(&mut scope).is_err());
+    assert_eq!(big.deserialize::<f64>(&mut scope).unwrap(), 18446744073709551616.0);
+}
+
+#[test]
+fn map_keys_round_trip() {
+    let mut scope = scope();
+    let levels = HashMap::from([(1u32, "debug".to_owned()), (2, "info".to_owned())]);
+    let names = BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]);
+
+    // non-string keys become property names, and are parsed back
+    let options = SerializeOptions::default();
+    let value = Value::serialize_with_options(&mut scope, &levels, &options).unwrap();
+    assert!(matches!(value, Value::Object(_)));
+    assert_eq!(round_trip(&mut scope, &levels, &options), levels);
+
+    let options = SerializeOptions {
+        non_string_keys_as_map: true,
+        ..Default::default()
+    };
+    let value = Value::serialize_with_options(&mut scope, &levels, &options).unwrap();
+    assert!(matches!(value, Value::Map(_)));
+    assert_eq!(round_trip(&mut scope, &levels, &options), levels);
+    // string keys stay an object
+    let value = Value::serialize_with_options(&mut scope, &names, &options).unwrap();
+    assert!(matches!(value, Value::Object(_)));
+    assert_eq!(round_trip(&mut scope, &names, &options), names);
+}
+
+#[test]
+fn proto_keys_become_own_properties() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(
+        r#"
+        exports.check = function(object) {
+            return Object.getPrototypeOf(object) === Object.prototype
+                && Object.keys(object).join(",") === "__proto__,a";
+        }
+        "#,
+    )
+    .unwrap();
+    let exports = exports.into_object().unwrap();
+    let check = exports.get(&mut scope, "check").unwrap().into_function().unwrap();
+    let options = SerializeOptions::default();
+
+    let map = BTreeMap::from([("__proto__".to_owned(), 1), ("a".to_owned(), 2)]);
+    let value = Value::serialize_with_options(&mut scope, &map, &options).unwrap();
+    let result = check.call(&mut scope, &[value]).unwrap();
+    assert_eq!(result.into_bool(), Some(true));
+    assert_eq!(round_trip(&mut scope, &map, &options), map);
+}
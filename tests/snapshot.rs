@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use unijs::{JsError, Runtime, RuntimeOptions, Snapshot, SnapshotBuilder};
+
+fn build() -> Snapshot {
+    SnapshotBuilder::new()
+        .with("spin.js", "function spin() { for (;;) {} }")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn snapshot_globals_are_available() {
+    unijs::init();
+    let snapshot = Snapshot::from_bytes(build().into_bytes());
+    let mut runtime = Runtime::from_snapshot(&snapshot, &RuntimeOptions::default()).unwrap();
+    let module = runtime.load("exports.kind = typeof spin;").unwrap();
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let kind = exports.get(&mut scope, "kind").unwrap();
+    assert_eq!(kind.into_string().as_deref(), Some("function"));
+}
+
+#[test]
+fn invalid_snapshots_are_rejected() {
+    unijs::init();
+    let options = RuntimeOptions::default();
+    let bytes = build().into_bytes();
+
+    let truncated = Snapshot::from_bytes(bytes[..bytes.len() / 2].to_vec());
+    assert!(Runtime::from_snapshot(&truncated, &options).is_err());
+
+    let mut corrupted = bytes.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0xff;
+    assert!(Runtime::from_snapshot(&Snapshot::from_bytes(corrupted), &options).is_err());
+
+    let garbage = Snapshot::from_bytes(b"not a snapshot".to_vec());
+    assert!(Runtime::from_snapshot(&garbage, &options).is_err());
+}
+
+#[test]
+fn snapshot_runtimes_take_options() {
+    unijs::init();
+    let options = RuntimeOptions {
+        timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    };
+    let mut runtime = Runtime::from_snapshot(&build(), &options).unwrap();
+    let error = runtime.load("spin();").unwrap_err();
+    assert!(matches!(error, JsError::Terminated));
+}
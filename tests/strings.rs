@@ -0,0 +1,108 @@
+use unijs::{JsString, Module, Scope, Value};
+
+const JS: &str = r#"
+    exports.echo = function(string) {
+        return string;
+    }
+    exports.codes = function(string) {
+        return Array.from({ length: string.length }, (_, i) => string.charCodeAt(i));
+    }
+    exports.fromCodes = function(codes) {
+        return String.fromCharCode(...codes);
+    }
+"#;
+
+fn call(scope: &mut Scope, exports: &Value, name: &str, args: &[Value]) -> Value {
+    let function = exports
+        .clone()
+        .into_object()
+        .unwrap()
+        .get(scope, name)
+        .unwrap()
+        .into_function()
+        .unwrap();
+    function.call(scope, args).unwrap()
+}
+
+#[test]
+fn lone_surrogates_are_kept() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    for codes in [vec![0x61, 0xD800, 0x62], vec![0xDC00], vec![0xDFFF, 0xD800]] {
+        let array = Value::serialize(&mut scope, &codes).unwrap();
+        let string = call(&mut scope, &exports, "fromCodes", &[array]);
+        assert!(matches!(string, Value::IllFormedString(_)));
+        assert!(string.clone().is_string());
+        let js_string = string.clone().into_js_string().unwrap();
+        assert!(!js_string.is_well_formed());
+        assert_eq!(js_string.to_utf16(), codes);
+        assert_eq!(js_string.len_utf16(), codes.len());
+        // the lossy string replaces each lone surrogate
+        let lossy = string.into_string().unwrap();
+        assert_eq!(lossy, String::from_utf16_lossy(&codes));
+    }
+}
+
+#[test]
+fn lone_surrogates_round_trip() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let string = JsString::from_utf16(vec![0xDC00, 0x41, 0xD83D]);
+    let echoed = call(&mut scope, &exports, "echo", &[string.clone().into()]);
+    assert_eq!(echoed.into_js_string().unwrap(), string);
+    let codes = call(&mut scope, &exports, "codes", &[string.into()])
+        .deserialize::<Vec<u16>>(&mut scope)
+        .unwrap();
+    assert_eq!(codes, [0xDC00, 0x41, 0xD83D]);
+}
+
+#[test]
+fn astral_characters_are_surrogate_pairs() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let emoji = "😀🦀𝄞".to_owned();
+    let echoed = call(&mut scope, &exports, "echo", &[Value::String(emoji.clone())]);
+    assert!(matches!(&echoed, Value::String(string) if *string == emoji));
+    let codes = call(&mut scope, &exports, "codes", &[Value::String(emoji.clone())])
+        .deserialize::<Vec<u16>>(&mut scope)
+        .unwrap();
+    assert_eq!(codes, emoji.encode_utf16().collect::<Vec<_>>());
+    assert_eq!(JsString::new(emoji).len_utf16(), 6);
+}
+
+#[test]
+fn well_formed_strings_are_plain_strings() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    for string in ["", "ascii", "latin-1 é ÿ", "日本語", "😀"] {
+        let echoed = call(&mut scope, &exports, "echo", &[Value::String(string.to_owned())]);
+        assert!(matches!(&echoed, Value::String(echoed) if echoed == string));
+        let js_string = echoed.into_js_string().unwrap();
+        assert!(js_string.is_well_formed());
+        assert_eq!(js_string, string);
+    }
+}
+
+#[test]
+fn very_long_strings_are_whole() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    // multi-byte characters straddle any fixed size buffer
+    for unit in ["a", "é", "日", "😀"] {
+        let string = unit.repeat(1 << 20);
+        let echoed = call(&mut scope, &exports, "echo", &[Value::String(string.clone())]);
+        assert_eq!(echoed.into_string().unwrap(), string);
+    }
+
+    let (mut scope, exports) =
+        Module::load(r#"exports.long = "x".repeat(3000000) + "\uD800";"#).unwrap();
+    let long = exports
+        .into_object()
+        .unwrap()
+        .get(&mut scope, "long")
+        .unwrap()
+        .into_js_string()
+        .unwrap();
+    assert_eq!(long.len_utf16(), 3000001);
+    assert_eq!(long.to_utf16().last(), Some(&0xD800));
+}
@@ -0,0 +1,74 @@
+use unijs::{Module, Object, PropertyKey, Symbol, Value};
+
+const JS: &str = r#"
+    exports.secret = Symbol("secret");
+    exports.object = { name: "object", [exports.secret]: 1 };
+    Object.defineProperty(exports.object, Symbol("hidden"), { value: 2, enumerable: false });
+    exports.same = function(a, b) {
+        return a === b;
+    }
+"#;
+
+#[test]
+fn symbol_keys_are_read_and_written() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let secret = exports.get(&mut scope, "secret").unwrap().into_symbol().unwrap();
+    let object = exports.get(&mut scope, "object").unwrap().into_object().unwrap();
+    assert_eq!(secret.description(&mut scope).as_deref(), Some("secret"));
+    assert_eq!(object.get(&mut scope, &secret).unwrap().into_number(), Some(1.0));
+
+    // a symbol with the same description is a different key
+    let other = Symbol::new(&mut scope, Some("secret"));
+    assert!(object.get(&mut scope, &other).unwrap().is_undefined());
+    object.set(&mut scope, &other, Value::Number(3.0)).unwrap();
+    assert_eq!(object.get(&mut scope, &secret).unwrap().into_number(), Some(1.0));
+    assert_eq!(object.get(&mut scope, &other).unwrap().into_number(), Some(3.0));
+}
+
+#[test]
+fn property_keys_include_enumerable_symbols() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let object = exports.get(&mut scope, "object").unwrap().into_object().unwrap();
+
+    assert_eq!(object.keys(&mut scope).unwrap(), vec!["name".to_owned()]);
+    let keys = object.property_keys(&mut scope).unwrap();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].clone().into_string().as_deref(), Some("name"));
+    let symbol = keys[1].clone().into_symbol().unwrap();
+    assert_eq!(symbol.description(&mut scope).as_deref(), Some("secret"));
+}
+
+#[test]
+fn registered_symbols_are_shared() {
+    unijs::init();
+    let (mut scope, exports) = Module::load(JS).unwrap();
+    let exports = exports.into_object().unwrap();
+    let same = exports.get(&mut scope, "same").unwrap().into_function().unwrap();
+
+    let first = Symbol::for_key(&mut scope, "app.id");
+    let second = Symbol::for_key(&mut scope, "app.id");
+    let result = same.call(&mut scope, &[first.into(), second.into()]).unwrap();
+    assert_eq!(result.into_bool(), Some(true));
+
+    let first = Symbol::new(&mut scope, Some("app.id"));
+    let second = Symbol::new(&mut scope, Some("app.id"));
+    let result = same.call(&mut scope, &[first.into(), second.into()]).unwrap();
+    assert_eq!(result.into_bool(), Some(false));
+}
+
+#[test]
+fn well_known_symbols_customize_objects() {
+    unijs::init();
+    let (mut scope, _) = Module::load("").unwrap();
+    let object = Object::new(&mut scope);
+    let tag = Symbol::to_string_tag(&mut scope);
+    object
+        .set(&mut scope, tag, Value::String("Custom".to_owned()))
+        .unwrap();
+    let keys = object.property_keys(&mut scope).unwrap();
+    assert!(matches!(&keys[..], [PropertyKey::Symbol(..)]));
+}
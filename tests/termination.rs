@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+use unijs::{JsError, Runtime, Value};
+
+const JS: &str = r#"
+    exports.spin = function() {
+        for (;;) {}
+    }
+    exports.add = function(a, b) {
+        return a + b;
+    }
+"#;
+
+#[test]
+fn runtime_timeout_terminates_and_recovers() {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let module = runtime.load(JS).unwrap();
+    runtime.set_timeout(Some(Duration::from_millis(50)));
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let spin = exports.get(&mut scope, "spin").unwrap().into_function().unwrap();
+    let add = exports.get(&mut scope, "add").unwrap().into_function().unwrap();
+
+    assert!(matches!(spin.call(&mut scope, &[]), Err(JsError::Terminated)));
+    let sum = add
+        .call(&mut scope, &[Value::Number(1.0), Value::Number(2.0)])
+        .unwrap();
+    assert_eq!(sum.into_number(), Some(3.0));
+}
+
+#[test]
+fn call_timeout_terminates_and_recovers() {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let module = runtime.load(JS).unwrap();
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let spin = exports.get(&mut scope, "spin").unwrap().into_function().unwrap();
+    let add = exports.get(&mut scope, "add").unwrap().into_function().unwrap();
+
+    let result = spin.call_with_timeout(&mut scope, &[], Duration::from_millis(50));
+    assert!(matches!(result, Err(JsError::Terminated)));
+    let sum = add
+        .call_with_timeout(
+            &mut scope,
+            &[Value::Number(1.0), Value::Number(2.0)],
+            Duration::from_millis(50),
+        )
+        .unwrap();
+    assert_eq!(sum.into_number(), Some(3.0));
+}
+
+#[test]
+fn call_timeout_applies_within_a_longer_runtime_timeout() {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let module = runtime.load(JS).unwrap();
+    runtime.set_timeout(Some(Duration::from_secs(60)));
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let spin = exports.get(&mut scope, "spin").unwrap().into_function().unwrap();
+
+    let start = Instant::now();
+    let result = spin.call_with_timeout(&mut scope, &[], Duration::from_millis(50));
+    assert!(matches!(result, Err(JsError::Terminated)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn many_calls_with_a_timeout_stay_fast() {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let module = runtime.load(JS).unwrap();
+    runtime.set_timeout(Some(Duration::from_secs(1)));
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let add = exports.get(&mut scope, "add").unwrap().into_function().unwrap();
+
+    for i in 0..10_000 {
+        let sum = add
+            .call(&mut scope, &[Value::Number(i as f64), Value::Number(1.0)])
+            .unwrap();
+        assert_eq!(sum.into_number(), Some(i as f64 + 1.0));
+    }
+}
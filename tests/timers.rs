@@ -0,0 +1,70 @@
+use unijs::Runtime;
+
+fn run(js: &str) -> serde_json::Value {
+    unijs::init();
+    let mut runtime = Runtime::new();
+    let module = runtime.load(js).unwrap();
+    runtime.run_until_idle().unwrap();
+    let mut scope = runtime.scope(module.context());
+    let exports = module.exports().clone().into_object().unwrap();
+    let log = exports.get(&mut scope, "log").unwrap();
+    log.into_json(&mut scope).unwrap().unwrap()
+}
+
+#[test]
+fn timers_fire_in_deadline_order() {
+    let log = run(r#"
+        exports.log = [];
+        setTimeout(() => exports.log.push(3), 30);
+        setTimeout(() => exports.log.push(1), 10);
+        setTimeout(() => exports.log.push(2), 20);
+        exports.log.push(0);
+    "#);
+    assert_eq!(log, serde_json::json!([0, 1, 2, 3]));
+}
+
+#[test]
+fn many_timers_complete() {
+    let log = run(r#"
+        exports.log = [];
+        let fired = 0;
+        for (let i = 0; i < 1000; i++) {
+            setTimeout(() => {
+                if (++fired === 1000) {
+                    exports.log.push(fired);
+                }
+            }, i % 20);
+        }
+    "#);
+    assert_eq!(log, serde_json::json!([1000]));
+}
+
+#[test]
+fn cleared_timers_dont_fire() {
+    let log = run(r#"
+        exports.log = [];
+        const cleared = setTimeout(() => exports.log.push("cleared"), 10);
+        setTimeout(() => exports.log.push("kept"), 20);
+        clearTimeout(cleared);
+        let ticks = 0;
+        const interval = setInterval(() => {
+            exports.log.push(`tick ${++ticks}`);
+            if (ticks === 2) {
+                clearInterval(interval);
+            }
+        }, 5);
+    "#);
+    assert_eq!(log, serde_json::json!(["tick 1", "tick 2", "kept"]));
+}
+
+#[test]
+fn timer_ids_are_unique_and_truthy() {
+    let log = run(r#"
+        const ids = new Set();
+        for (let i = 0; i < 100; i++) {
+            ids.add(setTimeout(() => {}, 0));
+        }
+        exports.log = [ids.size, [...ids].every(id => id > 0)];
+    "#);
+    assert_eq!(log, serde_json::json!([100, true]));
+}